@@ -0,0 +1,278 @@
+//! Known-good answers, recorded in `answers.toml` once a star is earned, so
+//! refactors can be checked against them with `aoc verify`.
+
+use std::{
+    error::Error,
+    fmt::{self, Display},
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::runner::{Part, PartResult};
+
+pub const ANSWERS_FILE: &str = "answers.toml";
+
+const HEADER: &str = "# Accepted answers, checked by `aoc verify`.\n\n";
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Answers {
+    #[serde(default, rename = "answer")]
+    entries: Vec<Entry>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Entry {
+    pub day: u8,
+    pub part: Part,
+    pub answer: String,
+    /// Hash of the input the answer was accepted for, see `input::hash`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub input_hash: Option<String>,
+}
+
+impl Answers {
+    /// Reads the registry at `path`, which is empty if the file does not
+    /// exist yet.
+    pub fn load(path: &Path) -> Result<Self, AnswersError> {
+        let contents = match fs::read_to_string(path) {
+            Ok(contents) => contents,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(Self::default()),
+            Err(error) => {
+                return Err(AnswersError::Io {
+                    path: path.to_path_buf(),
+                    error,
+                })
+            }
+        };
+
+        toml::from_str(&contents).map_err(|error| AnswersError::Parse {
+            path: path.to_path_buf(),
+            error,
+        })
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), AnswersError> {
+        let contents = toml::to_string(self).expect("answers are always valid TOML");
+
+        fs::write(path, String::from(HEADER) + &contents).map_err(|error| AnswersError::Io {
+            path: path.to_path_buf(),
+            error,
+        })
+    }
+
+    /// Adds an accepted answer, replacing any earlier one for the same part,
+    /// and keeps the entries in day and part order.
+    pub fn record(&mut self, entry: Entry) {
+        self.entries
+            .retain(|x| x.day != entry.day || x.part != entry.part);
+        self.entries.push(entry);
+        self.entries.sort_by_key(|x| (x.day, x.part));
+    }
+
+    pub fn get(&self, day: u8, part: Part) -> Option<&Entry> {
+        self.entries.iter().find(|x| x.day == day && x.part == part)
+    }
+
+    /// Compares a fresh result against the recorded answer for its part.
+    pub fn verify(&self, result: &PartResult) -> Verdict {
+        let entry = match self.get(result.day, result.part) {
+            Some(entry) => entry,
+            None => return Verdict::Missing,
+        };
+
+        if entry
+            .input_hash
+            .as_ref()
+            .is_some_and(|x| *x != result.input_hash)
+        {
+            return Verdict::OtherInput;
+        }
+
+        if result.answer.as_ref() == Some(&entry.answer) {
+            Verdict::Pass
+        } else {
+            Verdict::Fail {
+                expected: entry.answer.clone(),
+            }
+        }
+    }
+}
+
+/// `answers.toml` in the working directory if there is one, otherwise the
+/// crate's own.
+pub fn default_path() -> PathBuf {
+    crate::project_file(ANSWERS_FILE)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    Pass,
+    Fail {
+        expected: String,
+    },
+    /// No answer has been recorded for the part yet.
+    Missing,
+    /// The recorded answer belongs to a different input.
+    OtherInput,
+    /// The day could not be run at all.
+    Error(String),
+}
+
+impl Verdict {
+    pub fn is_failure(&self) -> bool {
+        matches!(self, Verdict::Fail { .. } | Verdict::Error(_))
+    }
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Verdict::Pass => f.pad("pass"),
+            Verdict::Fail { .. } => f.pad("FAIL"),
+            Verdict::Missing => f.pad("missing"),
+            Verdict::OtherInput => f.pad("other input"),
+            Verdict::Error(_) => f.pad("ERROR"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Check {
+    pub day: u8,
+    pub part: Part,
+    pub answer: Option<String>,
+    pub verdict: Verdict,
+}
+
+/// Renders checks as an aligned table followed by a pass/fail/missing count.
+pub struct Report<'a>(pub &'a [Check]);
+
+impl Display for Report<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let answers: Vec<_> = self
+            .0
+            .iter()
+            .map(|x| x.answer.as_deref().unwrap_or("unsolved"))
+            .collect();
+        let width = answers
+            .iter()
+            .map(|x| x.len())
+            .chain(["Answer".len()])
+            .max()
+            .unwrap_or_default();
+
+        writeln!(f, "Day  Part  Result       {:<width$}  Expected", "Answer")?;
+
+        for (check, answer) in self.0.iter().zip(answers) {
+            write!(
+                f,
+                "{:>3}  {:>4}  {:<11}  ",
+                check.day, check.part, check.verdict
+            )?;
+
+            match &check.verdict {
+                Verdict::Fail { expected } => writeln!(f, "{:<width$}  {}", answer, expected)?,
+                Verdict::Error(error) => writeln!(f, "{}", error)?,
+                _ => writeln!(f, "{}", answer)?,
+            }
+        }
+
+        let count = |func: fn(&Verdict) -> bool| self.0.iter().filter(|x| func(&x.verdict)).count();
+        writeln!(
+            f,
+            "\n{} passed, {} failed, {} missing",
+            count(|x| *x == Verdict::Pass),
+            count(Verdict::is_failure),
+            count(|x| matches!(x, Verdict::Missing | Verdict::OtherInput)),
+        )
+    }
+}
+
+#[derive(Debug)]
+pub enum AnswersError {
+    Io {
+        path: PathBuf,
+        error: io::Error,
+    },
+    Parse {
+        path: PathBuf,
+        error: toml::de::Error,
+    },
+}
+
+impl Display for AnswersError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AnswersError::Io { path, error } => {
+                write!(f, "failed to access {}: {}", path.display(), error)
+            }
+            AnswersError::Parse { path, error } => {
+                write!(f, "invalid answers file {}: {}", path.display(), error)
+            }
+        }
+    }
+}
+
+impl Error for AnswersError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            AnswersError::Io { error, .. } => Some(error),
+            AnswersError::Parse { error, .. } => Some(error),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    const ANSWERS: &str = r#"
+[[answer]]
+day = 1
+part = 1
+answer = "11"
+
+[[answer]]
+day = 1
+part = 2
+answer = "31"
+input_hash = "0123456789abcdef"
+"#;
+
+    fn result(part: Part, answer: &str, input_hash: &str) -> PartResult {
+        PartResult {
+            day: 1,
+            part,
+            answer: Some(String::from(answer)),
+            timed_out: false,
+            elapsed: Duration::ZERO,
+            parse_elapsed: Duration::ZERO,
+            input_hash: String::from(input_hash),
+        }
+    }
+
+    #[test]
+    fn verdicts() {
+        let answers: Answers = toml::from_str(ANSWERS).unwrap();
+
+        assert_eq!(answers.verify(&result(Part::One, "11", "x")), Verdict::Pass);
+        assert_eq!(
+            answers.verify(&result(Part::One, "12", "x")),
+            Verdict::Fail {
+                expected: String::from("11")
+            }
+        );
+        assert_eq!(
+            answers.verify(&result(Part::Two, "31", "fedcba9876543210")),
+            Verdict::OtherInput
+        );
+        assert_eq!(
+            answers.verify(&result(Part::Two, "31", "0123456789abcdef")),
+            Verdict::Pass
+        );
+    }
+}
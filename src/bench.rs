@@ -0,0 +1,237 @@
+//! Repeated timing of each phase of a day, without an external harness.
+
+use std::{
+    fmt::{self, Display},
+    hint::black_box,
+    time::{Duration, Instant},
+};
+
+use crate::{runner::Part, Answer, ParseError, Solution};
+
+/// Every phase is sampled at least this often, however slow it is.
+const MIN_SAMPLES: usize = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Options {
+    /// How long to run each phase before measuring it.
+    pub warmup: Duration,
+    /// How long to keep measuring each phase.
+    pub measure: Duration,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            warmup: Duration::from_secs(1),
+            measure: Duration::from_secs(3),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Phase {
+    Parse,
+    Part(Part),
+}
+
+impl Display for Phase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Phase::Parse => f.pad("parse"),
+            Phase::Part(Part::One) => f.pad("part 1"),
+            Phase::Part(Part::Two) => f.pad("part 2"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Stats {
+    pub samples: usize,
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub p95: Duration,
+    pub std_dev: Duration,
+}
+
+impl Stats {
+    /// Summarises a non-empty set of samples.
+    pub fn from_samples(mut samples: Vec<Duration>) -> Self {
+        assert!(!samples.is_empty());
+        samples.sort();
+
+        let n = samples.len();
+        let median = if n.is_multiple_of(2) {
+            (samples[n / 2 - 1] + samples[n / 2]) / 2
+        } else {
+            samples[n / 2]
+        };
+
+        let mean = samples.iter().sum::<Duration>() / n as u32;
+        let variance = if n > 1 {
+            samples
+                .iter()
+                .map(|x| (x.as_secs_f64() - mean.as_secs_f64()).powi(2))
+                .sum::<f64>()
+                / (n - 1) as f64
+        } else {
+            0.0
+        };
+
+        // Nearest rank, so p95 is always one of the samples
+        let p95 = samples[(n * 95).div_ceil(100) - 1];
+
+        Self {
+            samples: n,
+            min: samples[0],
+            median,
+            mean,
+            p95,
+            std_dev: Duration::from_secs_f64(variance.sqrt()),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct PhaseStats {
+    pub day: u8,
+    pub phase: Phase,
+    /// `None` when the phase is a part that has not been solved yet.
+    pub stats: Option<Stats>,
+}
+
+/// Runs `func` for the warmup period, then samples it for the measuring
+/// period.
+pub fn measure<T, F>(options: &Options, mut func: F) -> Stats
+where
+    F: FnMut() -> T,
+{
+    let start = Instant::now();
+    while start.elapsed() < options.warmup {
+        black_box(func());
+    }
+
+    let mut samples = vec![];
+    let start = Instant::now();
+    while start.elapsed() < options.measure || samples.len() < MIN_SAMPLES {
+        let sample = Instant::now();
+        black_box(func());
+        samples.push(sample.elapsed());
+    }
+
+    Stats::from_samples(samples)
+}
+
+pub(crate) fn run<S: Solution>(
+    contents: &str,
+    parts: &[Part],
+    options: &Options,
+) -> Result<Vec<PhaseStats>, ParseError> {
+    let input = S::parse(contents)?;
+
+    let mut results = vec![PhaseStats {
+        day: S::DAY,
+        phase: Phase::Parse,
+        stats: Some(measure(options, || S::parse(black_box(contents)))),
+    }];
+
+    for &part in parts {
+        let stats = match part {
+            Part::One => measure_part(options, || S::part1(black_box(&input))),
+            Part::Two => measure_part(options, || S::part2(black_box(&input))),
+        };
+
+        results.push(PhaseStats {
+            day: S::DAY,
+            phase: Phase::Part(part),
+            stats,
+        });
+    }
+
+    Ok(results)
+}
+
+fn measure_part<A, F>(options: &Options, mut func: F) -> Option<Stats>
+where
+    A: Answer,
+    F: FnMut() -> A,
+{
+    func().is_solved().then(|| measure(options, func))
+}
+
+/// Renders phase statistics as an aligned table.
+pub struct Table<'a>(pub &'a [PhaseStats]);
+
+impl Display for Table<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        const COLUMNS: [&str; 6] = ["Samples", "Min", "Median", "Mean", "p95", "Std dev"];
+
+        let rows: Vec<_> = self
+            .0
+            .iter()
+            .map(|x| {
+                x.stats.map(|stats| {
+                    [
+                        stats.samples.to_string(),
+                        format!("{:.2?}", stats.min),
+                        format!("{:.2?}", stats.median),
+                        format!("{:.2?}", stats.mean),
+                        format!("{:.2?}", stats.p95),
+                        format!("{:.2?}", stats.std_dev),
+                    ]
+                })
+            })
+            .collect();
+
+        let widths: Vec<_> = (0..COLUMNS.len())
+            .map(|i| {
+                rows.iter()
+                    .flatten()
+                    .map(|x| x[i].len())
+                    .chain([COLUMNS[i].len()])
+                    .max()
+                    .unwrap_or_default()
+            })
+            .collect();
+
+        write!(f, "Day  Phase ")?;
+        for (column, width) in COLUMNS.iter().zip(&widths) {
+            write!(f, "  {:>width$}", column)?;
+        }
+        writeln!(f)?;
+
+        for (result, row) in self.0.iter().zip(rows) {
+            write!(f, "{:>3}  {:<6}", result.day, result.phase)?;
+
+            match row {
+                Some(row) => {
+                    for (value, width) in row.iter().zip(&widths) {
+                        write!(f, "  {:>width$}", value)?;
+                    }
+                    writeln!(f)?;
+                }
+                None => writeln!(f, "  unsolved")?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stats() {
+        let samples = (1..=20).rev().map(Duration::from_millis).collect();
+        let stats = Stats::from_samples(samples);
+
+        assert_eq!(stats.samples, 20);
+        assert_eq!(stats.min, Duration::from_millis(1));
+        assert_eq!(stats.median, Duration::from_micros(10_500));
+        assert_eq!(stats.mean, Duration::from_micros(10_500));
+        assert_eq!(stats.p95, Duration::from_millis(19));
+        assert_eq!(stats.std_dev.as_micros(), 5916);
+    }
+}
@@ -0,0 +1,1106 @@
+mod scaffold;
+mod watch;
+
+use std::{
+    env,
+    error::Error,
+    io,
+    process::ExitCode,
+    time::{Duration, Instant},
+};
+
+use itertools::Itertools;
+
+use advent_of_code_2024::{
+    answers::{self, Answers, Check, Entry, Report, Verdict},
+    bench,
+    client::{self, Fetched},
+    config::Config,
+    days::{
+        self,
+        day1::{
+            external::{self, ExternalError},
+            online::{self, OnlineError},
+            Day1, Weighting,
+        },
+        day2::{Day2, Diagnosis},
+    },
+    differential, generate, input,
+    input::Source,
+    parallel::{self, prelude::*},
+    profile,
+    runner::{Day, Failure, Format, Part, PartResult, Summary},
+    submit::{self, History, Outcome},
+    ParseError, Solution,
+};
+
+const USAGE: &str = "\
+usage: aoc run <day|all> [options]
+       aoc bench <day> [options] [bench options]
+       aoc profile <day|all> [options]
+       aoc watch <day> [options] [-n <secs>]
+       aoc gen <day> [--seed <n>] [--size <n>]
+       aoc compare <day|all> [--seeds <n>] [--size <n>]
+       aoc external <day> [options] [-c <lines>]
+       aoc online <day> [options]
+       aoc similarity <day> [options] [-k <n>] [--weighted]
+       aoc dampener <day> [options] [-k <n>]
+       aoc explain <day> [options]
+       aoc verify [day|all] [-b <secs>]
+       aoc new <day>
+       aoc fetch <day>
+       aoc submit <day> <1|2>
+
+Options:
+  -p, --part <1|2>       only run one part
+  -i, --input <path|->   read the input from a file, or stdin for `-`
+  -v, --variant <name>   read the named input variant, e.g. `example`
+  -f, --format <format>  print `table` (the default), `json` or `csv`, only for `run`
+  -j, --parallel         run days at the same time, only for `run all`
+  -b, --budget <secs>    stop each part after this long, only for `run` and
+                         `verify`
+
+Options for every command:
+  --threads <n>          threads for the parallel parts of days (default one
+                         per core), 1 for deterministic timings
+
+Bench options:
+  -w, --warmup <secs>    run each phase this long before measuring (default 1)
+  -t, --time <secs>      measure each phase for this long (default 3)
+
+Watch options:
+  -n, --interval <secs>  how often to check for changes (default 1)
+
+Named inputs are looked up as `dayN.txt` or `dayN.<variant>.txt` in
+$AOC_INPUT_DIR first, then in `src/inputs`.
+
+`run all` carries on past days that fail and ends with a summary of the
+total time, the slowest parts and the failures.
+`profile` reports the time, allocation count, bytes allocated and peak live
+bytes of each phase; allocations are only counted when built with
+`--features count-alloc`.
+`watch` runs a day again whenever its module or input changes, and shows how
+the answers compare with the previous run.
+`gen` prints a random input for a day, the same one for the same seed (default
+0) and size (default 100). What the size counts depends on the day.
+`compare` checks days against brute force solutions on generated inputs of
+every size up to `--size` (default 20), `--seeds` (default 10) of each, and
+prints the smallest input they disagree on.
+`external` solves day 1 without holding its input in memory, sorting
+`--chunk` lines (default 4194304) at a time into temporary files under
+$TMPDIR and merging them.
+`online` reads day 1 updates a line at a time and prints the total distance
+(`-` while the lists differ in length) and similarity score after each one.
+`<left> <right>` or `+ <left> <right>` inserts a pair, `- <left> <right>`
+removes one, and `+l`, `-l`, `+r` and `-r` followed by an ID change one list.
+`similarity` prints day 1's similarity score counting right IDs within
+`--tolerance` (default 0) of each left ID as matches, each `--weighted` by how
+close it is if given.
+`dampener` counts day 2's reports by the fewest levels that must be removed
+to make them safe, and how many are safe removing at most `--tolerance`
+(default 1).
+`explain` prints each of day 2's reports with markers under the first levels
+that make it unsafe, why, and which level to remove if that makes it safe.
+`verify` re-runs days against the answers recorded in `answers.toml`.
+`new` generates and registers the module for a day that does not exist yet.
+`fetch` downloads a day's input unless it is already cached, using the
+session token from $AOC_SESSION or `session` in `aoc.toml`.
+`submit` runs a part and submits its answer, unless earlier submissions show
+it is wrong. Accepted answers are recorded in `answers.toml`.";
+
+enum Target {
+    All,
+    Day(u8),
+}
+
+enum Command {
+    Run {
+        target: Target,
+        parts: Vec<Part>,
+        source: Source,
+        format: Format,
+        parallel: bool,
+        budget: Option<Duration>,
+    },
+    Bench {
+        day: u8,
+        parts: Vec<Part>,
+        source: Source,
+        options: bench::Options,
+    },
+    Profile {
+        target: Target,
+        parts: Vec<Part>,
+        source: Source,
+    },
+    Watch {
+        day: u8,
+        parts: Vec<Part>,
+        source: Source,
+        interval: Duration,
+    },
+    Gen {
+        day: u8,
+        seed: u64,
+        size: usize,
+    },
+    Compare {
+        target: Target,
+        options: differential::Options,
+    },
+    External {
+        day: u8,
+        parts: Vec<Part>,
+        source: Source,
+        options: external::Options,
+    },
+    Online {
+        day: u8,
+        source: Source,
+    },
+    Similarity {
+        day: u8,
+        source: Source,
+        tolerance: u32,
+        weighting: Weighting,
+    },
+    Dampener {
+        day: u8,
+        source: Source,
+        tolerance: usize,
+    },
+    Explain {
+        day: u8,
+        source: Source,
+    },
+    Verify {
+        target: Target,
+        budget: Option<Duration>,
+    },
+    New {
+        day: u8,
+    },
+    Fetch {
+        day: u8,
+    },
+    Submit {
+        day: u8,
+        part: Part,
+    },
+}
+
+/// The options shared by every command that runs a day.
+struct RunOptions {
+    parts: Vec<Part>,
+    source: Source,
+}
+
+impl Default for RunOptions {
+    fn default() -> Self {
+        Self {
+            parts: Part::ALL.to_vec(),
+            source: Source::default(),
+        }
+    }
+}
+
+impl RunOptions {
+    /// Applies `arg` if it is one of the shared options, taking its value
+    /// from `args`, and returns whether it was.
+    fn apply(
+        &mut self,
+        arg: &str,
+        args: &mut impl Iterator<Item = String>,
+    ) -> Result<bool, String> {
+        match arg {
+            "-p" | "--part" => {
+                let value = args.next().ok_or("missing value for `--part`")?;
+                let part = Part::parse(&value)
+                    .ok_or_else(|| format!("invalid part `{}`, expected 1 or 2", value))?;
+                self.parts = vec![part];
+            }
+            "-i" | "--input" => {
+                let value = args.next().ok_or("missing value for `--input`")?;
+                self.source = Source::from_arg(&value);
+            }
+            "-v" | "--variant" => {
+                let value = args.next().ok_or("missing value for `--variant`")?;
+                self.source = Source::Named(Some(value));
+            }
+            _ => return Ok(false),
+        }
+
+        Ok(true)
+    }
+}
+
+impl Command {
+    fn parse(mut args: impl Iterator<Item = String>) -> Result<Self, String> {
+        match args.next().as_deref() {
+            Some("run") => Self::parse_run(args),
+            Some("bench") => Self::parse_bench(args),
+            Some("profile") => Self::parse_profile(args),
+            Some("watch") => Self::parse_watch(args),
+            Some("gen") => Self::parse_gen(args),
+            Some("compare") => Self::parse_compare(args),
+            Some("external") => Self::parse_external(args),
+            Some("online") => Self::parse_online(args),
+            Some("similarity") => Self::parse_similarity(args),
+            Some("dampener") => Self::parse_dampener(args),
+            Some("explain") => Self::parse_explain(args),
+            Some("verify") => Self::parse_verify(args),
+            Some("new") => Self::parse_new(args),
+            Some("fetch") => Self::parse_fetch(args),
+            Some("submit") => Self::parse_submit(args),
+            Some(other) => Err(format!("unknown command `{}`", other)),
+            None => Err(String::from("missing command")),
+        }
+    }
+
+    fn parse_run(mut args: impl Iterator<Item = String>) -> Result<Self, String> {
+        let target = match args.next() {
+            Some(arg) => Target::parse(&arg)?,
+            None => return Err(String::from("missing day")),
+        };
+
+        let mut options = RunOptions::default();
+        let mut format = Format::default();
+        let mut parallel = false;
+        let mut budget = None;
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "-f" | "--format" => {
+                    let value = args.next().ok_or("missing value for `--format`")?;
+                    format = Format::parse(&value).ok_or_else(|| {
+                        format!("invalid format `{}`, expected table, json or csv", value)
+                    })?;
+                }
+                "-j" | "--parallel" => parallel = true,
+                "-b" | "--budget" => {
+                    budget = Some(parse_seconds(
+                        &args.next().ok_or("missing value for `--budget`")?,
+                    )?);
+                }
+                other => {
+                    if !options.apply(other, &mut args)? {
+                        return Err(format!("unexpected argument `{}`", other));
+                    }
+                }
+            }
+        }
+
+        if matches!(target, Target::All)
+            && matches!(options.source, Source::Path(_) | Source::Stdin)
+        {
+            return Err(String::from("`--input` can only be used with a single day"));
+        }
+        if parallel && !matches!(target, Target::All) {
+            return Err(String::from("`--parallel` can only be used with `all`"));
+        }
+
+        Ok(Self::Run {
+            target,
+            parts: options.parts,
+            source: options.source,
+            format,
+            parallel,
+            budget,
+        })
+    }
+
+    fn parse_bench(mut args: impl Iterator<Item = String>) -> Result<Self, String> {
+        let day = parse_day(&args.next().ok_or("missing day")?)?;
+
+        let mut options = RunOptions::default();
+        let mut bench_options = bench::Options::default();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "-w" | "--warmup" => {
+                    let value = args.next().ok_or("missing value for `--warmup`")?;
+                    bench_options.warmup = parse_seconds(&value)?;
+                }
+                "-t" | "--time" => {
+                    let value = args.next().ok_or("missing value for `--time`")?;
+                    bench_options.measure = parse_seconds(&value)?;
+                }
+                other => {
+                    if !options.apply(other, &mut args)? {
+                        return Err(format!("unexpected argument `{}`", other));
+                    }
+                }
+            }
+        }
+
+        Ok(Self::Bench {
+            day,
+            parts: options.parts,
+            source: options.source,
+            options: bench_options,
+        })
+    }
+
+    fn parse_profile(mut args: impl Iterator<Item = String>) -> Result<Self, String> {
+        let target = match args.next() {
+            Some(arg) => Target::parse(&arg)?,
+            None => return Err(String::from("missing day")),
+        };
+
+        let mut options = RunOptions::default();
+        while let Some(arg) = args.next() {
+            if !options.apply(&arg, &mut args)? {
+                return Err(format!("unexpected argument `{}`", arg));
+            }
+        }
+
+        if matches!(target, Target::All)
+            && matches!(options.source, Source::Path(_) | Source::Stdin)
+        {
+            return Err(String::from("`--input` can only be used with a single day"));
+        }
+
+        Ok(Self::Profile {
+            target,
+            parts: options.parts,
+            source: options.source,
+        })
+    }
+
+    fn parse_watch(mut args: impl Iterator<Item = String>) -> Result<Self, String> {
+        let day = parse_day(&args.next().ok_or("missing day")?)?;
+
+        let mut options = RunOptions::default();
+        let mut interval = Duration::from_secs(1);
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "-n" | "--interval" => {
+                    interval =
+                        parse_seconds(&args.next().ok_or("missing value for `--interval`")?)?;
+                }
+                other => {
+                    if !options.apply(other, &mut args)? {
+                        return Err(format!("unexpected argument `{}`", other));
+                    }
+                }
+            }
+        }
+
+        if options.source == Source::Stdin {
+            return Err(String::from("`watch` cannot read the input from stdin"));
+        }
+
+        Ok(Self::Watch {
+            day,
+            parts: options.parts,
+            source: options.source,
+            interval,
+        })
+    }
+
+    fn parse_gen(mut args: impl Iterator<Item = String>) -> Result<Self, String> {
+        let day = parse_day(&args.next().ok_or("missing day")?)?;
+
+        let mut seed = 0;
+        let mut size = generate::DEFAULT_SIZE;
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--seed" => {
+                    let value = args.next().ok_or("missing value for `--seed`")?;
+                    seed = value
+                        .parse()
+                        .map_err(|_| format!("invalid seed `{}`", value))?;
+                }
+                "--size" => {
+                    let value = args.next().ok_or("missing value for `--size`")?;
+                    size = value
+                        .parse()
+                        .map_err(|_| format!("invalid size `{}`", value))?;
+                }
+                other => return Err(format!("unexpected argument `{}`", other)),
+            }
+        }
+
+        Ok(Self::Gen { day, seed, size })
+    }
+
+    fn parse_compare(mut args: impl Iterator<Item = String>) -> Result<Self, String> {
+        let target = Target::parse(&args.next().ok_or("missing day")?)?;
+
+        let mut options = differential::Options::default();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--seeds" => {
+                    let value = args.next().ok_or("missing value for `--seeds`")?;
+                    options.seeds = value
+                        .parse()
+                        .map_err(|_| format!("invalid number of seeds `{}`", value))?;
+                }
+                "--size" => {
+                    let value = args.next().ok_or("missing value for `--size`")?;
+                    options.max_size = value
+                        .parse()
+                        .map_err(|_| format!("invalid size `{}`", value))?;
+                }
+                other => return Err(format!("unexpected argument `{}`", other)),
+            }
+        }
+
+        Ok(Self::Compare { target, options })
+    }
+
+    fn parse_external(mut args: impl Iterator<Item = String>) -> Result<Self, String> {
+        let day = parse_day(&args.next().ok_or("missing day")?)?;
+
+        let mut options = RunOptions::default();
+        let mut external_options = external::Options::default();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "-c" | "--chunk" => {
+                    let value = args.next().ok_or("missing value for `--chunk`")?;
+                    external_options.chunk_lines = value
+                        .parse()
+                        .ok()
+                        .filter(|&x| x > 0)
+                        .ok_or_else(|| format!("invalid number of lines `{}`", value))?;
+                }
+                other => {
+                    if !options.apply(other, &mut args)? {
+                        return Err(format!("unexpected argument `{}`", other));
+                    }
+                }
+            }
+        }
+
+        Ok(Self::External {
+            day,
+            parts: options.parts,
+            source: options.source,
+            options: external_options,
+        })
+    }
+
+    fn parse_online(mut args: impl Iterator<Item = String>) -> Result<Self, String> {
+        let day = parse_day(&args.next().ok_or("missing day")?)?;
+
+        let mut options = RunOptions::default();
+        while let Some(arg) = args.next() {
+            if !options.apply(&arg, &mut args)? {
+                return Err(format!("unexpected argument `{}`", arg));
+            }
+        }
+
+        if options.parts != Part::ALL {
+            return Err(String::from("`--part` cannot be used with `online`"));
+        }
+
+        Ok(Self::Online {
+            day,
+            source: options.source,
+        })
+    }
+
+    fn parse_similarity(mut args: impl Iterator<Item = String>) -> Result<Self, String> {
+        let day = parse_day(&args.next().ok_or("missing day")?)?;
+
+        let mut options = RunOptions::default();
+        let mut tolerance = 0;
+        let mut weighting = Weighting::Flat;
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "-k" | "--tolerance" => {
+                    let value = args.next().ok_or("missing value for `--tolerance`")?;
+                    tolerance = value
+                        .parse()
+                        .map_err(|_| format!("invalid tolerance `{}`", value))?;
+                }
+                "-w" | "--weighted" => weighting = Weighting::Linear,
+                other => {
+                    if !options.apply(other, &mut args)? {
+                        return Err(format!("unexpected argument `{}`", other));
+                    }
+                }
+            }
+        }
+
+        if options.parts != Part::ALL {
+            return Err(String::from("`--part` cannot be used with `similarity`"));
+        }
+
+        Ok(Self::Similarity {
+            day,
+            source: options.source,
+            tolerance,
+            weighting,
+        })
+    }
+
+    fn parse_dampener(mut args: impl Iterator<Item = String>) -> Result<Self, String> {
+        let day = parse_day(&args.next().ok_or("missing day")?)?;
+
+        let mut options = RunOptions::default();
+        let mut tolerance = 1;
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "-k" | "--tolerance" => {
+                    let value = args.next().ok_or("missing value for `--tolerance`")?;
+                    tolerance = value
+                        .parse()
+                        .map_err(|_| format!("invalid tolerance `{}`", value))?;
+                }
+                other => {
+                    if !options.apply(other, &mut args)? {
+                        return Err(format!("unexpected argument `{}`", other));
+                    }
+                }
+            }
+        }
+
+        if options.parts != Part::ALL {
+            return Err(String::from("`--part` cannot be used with `dampener`"));
+        }
+
+        Ok(Self::Dampener {
+            day,
+            source: options.source,
+            tolerance,
+        })
+    }
+
+    fn parse_explain(mut args: impl Iterator<Item = String>) -> Result<Self, String> {
+        let day = parse_day(&args.next().ok_or("missing day")?)?;
+
+        let mut options = RunOptions::default();
+        while let Some(arg) = args.next() {
+            if !options.apply(&arg, &mut args)? {
+                return Err(format!("unexpected argument `{}`", arg));
+            }
+        }
+
+        if options.parts != Part::ALL {
+            return Err(String::from("`--part` cannot be used with `explain`"));
+        }
+
+        Ok(Self::Explain {
+            day,
+            source: options.source,
+        })
+    }
+
+    fn parse_verify(mut args: impl Iterator<Item = String>) -> Result<Self, String> {
+        let mut target = None;
+        let mut budget = None;
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "-b" | "--budget" => {
+                    budget = Some(parse_seconds(
+                        &args.next().ok_or("missing value for `--budget`")?,
+                    )?);
+                }
+                other if target.is_none() => target = Some(Target::parse(other)?),
+                other => return Err(format!("unexpected argument `{}`", other)),
+            }
+        }
+
+        Ok(Self::Verify {
+            target: target.unwrap_or(Target::All),
+            budget,
+        })
+    }
+
+    fn parse_new(mut args: impl Iterator<Item = String>) -> Result<Self, String> {
+        let day = parse_day(&args.next().ok_or("missing day")?)?;
+
+        if let Some(other) = args.next() {
+            return Err(format!("unexpected argument `{}`", other));
+        }
+
+        Ok(Self::New { day })
+    }
+
+    fn parse_fetch(mut args: impl Iterator<Item = String>) -> Result<Self, String> {
+        let day = parse_day(&args.next().ok_or("missing day")?)?;
+
+        if let Some(other) = args.next() {
+            return Err(format!("unexpected argument `{}`", other));
+        }
+
+        Ok(Self::Fetch { day })
+    }
+
+    fn parse_submit(mut args: impl Iterator<Item = String>) -> Result<Self, String> {
+        let day = parse_day(&args.next().ok_or("missing day")?)?;
+        let value = args.next().ok_or("missing part")?;
+        let part = Part::parse(&value)
+            .ok_or_else(|| format!("invalid part `{}`, expected 1 or 2", value))?;
+
+        if let Some(other) = args.next() {
+            return Err(format!("unexpected argument `{}`", other));
+        }
+
+        Ok(Self::Submit { day, part })
+    }
+
+    fn execute(self) -> Result<(), Box<dyn Error>> {
+        match self {
+            Command::Run {
+                target,
+                parts,
+                source,
+                format,
+                parallel,
+                budget,
+            } => {
+                let Target::All = target else {
+                    let day = target.days()?[0];
+                    let contents = source.read(day.day)?;
+                    let results = day.run_with_budget(&contents, &parts, budget)?;
+                    print!("{}", format.render(&results));
+
+                    return timeouts(&results);
+                };
+
+                let run_day = |day: &Day| {
+                    source
+                        .read(day.day)
+                        .map_err(|x| x.to_string())
+                        .and_then(|contents| {
+                            day.run_with_budget(&contents, &parts, budget)
+                                .map_err(describe)
+                        })
+                        .map_err(|error| Failure {
+                            day: day.day,
+                            error,
+                        })
+                };
+
+                // Days share rayon's global pool with their own parallel
+                // loops, such as day 6's `count_all_loops`, so those split
+                // across whatever threads the other days leave idle rather
+                // than starting more threads than there are cores
+                let start = Instant::now();
+                let outcomes: Vec<_> = if parallel {
+                    days::REGISTRY.par_iter().map(run_day).collect()
+                } else {
+                    days::REGISTRY.iter().map(run_day).collect()
+                };
+                let wall = start.elapsed();
+
+                let mut results = vec![];
+                let mut failures = vec![];
+                for outcome in outcomes {
+                    match outcome {
+                        Ok(x) => results.extend(x),
+                        Err(x) => failures.push(x),
+                    }
+                }
+
+                print!("{}", format.render(&results));
+                if format == Format::Table {
+                    print!(
+                        "\n{}",
+                        Summary {
+                            results: &results,
+                            failures: &failures,
+                            wall,
+                        }
+                    );
+                } else {
+                    for failure in &failures {
+                        eprintln!("day {} failed: {}", failure.day, failure.error);
+                    }
+                }
+
+                if !failures.is_empty() {
+                    return Err(format!(
+                        "{} of {} days failed",
+                        failures.len(),
+                        days::REGISTRY.len()
+                    )
+                    .into());
+                }
+
+                timeouts(&results)
+            }
+            Command::Bench {
+                day,
+                parts,
+                source,
+                options,
+            } => {
+                let day = Target::Day(day).days()?[0];
+                let contents = source.read(day.day)?;
+                let results = day.bench(&contents, &parts, &options)?;
+
+                print!("{}", bench::Table(&results));
+
+                Ok(())
+            }
+            Command::Profile {
+                target,
+                parts,
+                source,
+            } => {
+                let mut results = vec![];
+                for day in target.days()? {
+                    let contents = source.read(day.day)?;
+                    results.extend(day.profile(&contents, &parts)?);
+                }
+
+                print!("{}", profile::Table(&results));
+
+                Ok(())
+            }
+            Command::Watch {
+                day,
+                parts,
+                source,
+                interval,
+            } => watch::watch(day, &parts, &source, interval),
+            Command::Gen { day, seed, size } => {
+                let day = Target::Day(day).days()?[0];
+                let contents = day
+                    .generate(seed, size)
+                    .ok_or_else(|| format!("day {} has no input generator", day.day))?;
+
+                print!("{}", contents);
+
+                Ok(())
+            }
+            Command::Compare { target, options } => {
+                let references: Vec<_> = differential::REFERENCES
+                    .iter()
+                    .filter(|x| match target {
+                        Target::All => true,
+                        Target::Day(day) => x.day == day,
+                    })
+                    .collect();
+                if let (Target::Day(day), []) = (&target, &references[..]) {
+                    return Err(format!("day {} has no brute force to compare with", day).into());
+                }
+
+                let total = references.len();
+                let mut failed = 0;
+                for reference in references {
+                    match differential::check(reference, &options) {
+                        Ok(count) => println!(
+                            "day {} part {}: agrees on {} inputs",
+                            reference.day, reference.part, count
+                        ),
+                        Err(disagreement) => {
+                            println!("{}", disagreement);
+                            failed += 1;
+                        }
+                    }
+                }
+
+                if failed > 0 {
+                    return Err(format!("{} of {} parts disagree", failed, total).into());
+                }
+
+                Ok(())
+            }
+            Command::External {
+                day,
+                parts,
+                source,
+                options,
+            } => {
+                if day != 1 {
+                    return Err(format!("day {} has no external-memory mode", day).into());
+                }
+
+                let start = Instant::now();
+                let totals = external::solve(source.open(day)?, &options).map_err(|x| match x {
+                    ExternalError::Parse(error) => describe(error),
+                    other => other.to_string(),
+                })?;
+                let elapsed = start.elapsed();
+
+                println!("Day  Part  Answer");
+                for part in parts {
+                    let answer = match part {
+                        Part::One => totals.distance,
+                        Part::Two => totals.similarity,
+                    };
+                    println!("{:>3}  {:>4}  {}", day, part, answer);
+                }
+                println!(
+                    "\n{} pairs, {} sorted runs on disk per list, {:.2?}",
+                    totals.pairs, totals.runs, elapsed
+                );
+
+                Ok(())
+            }
+            Command::Online { day, source } => {
+                if day != 1 {
+                    return Err(format!("day {} has no online mode", day).into());
+                }
+
+                online::run(source.open(day)?, io::stdout().lock()).map_err(|x| match x {
+                    OnlineError::Parse(error) => describe(error),
+                    other => other.to_string(),
+                })?;
+
+                Ok(())
+            }
+            Command::Similarity {
+                day,
+                source,
+                tolerance,
+                weighting,
+            } => {
+                if day != 1 {
+                    return Err(format!("day {} has no tolerant similarity score", day).into());
+                }
+
+                let input = Day1::parse(&source.read(day)?).map_err(describe)?;
+                println!("{}", input.tolerant_similarity_score(tolerance, weighting));
+
+                Ok(())
+            }
+            Command::Dampener {
+                day,
+                source,
+                tolerance,
+            } => {
+                if day != 2 {
+                    return Err(format!("day {} has no Problem Dampener", day).into());
+                }
+
+                let rows = Day2::parse(&source.read(day)?).map_err(describe)?;
+                let removals = rows.iter().map(|x| x.min_removals()).counts();
+
+                println!("Removed  Reports");
+                for (removed, count) in removals.iter().sorted() {
+                    println!("{:>7}  {:>7}", removed, count);
+                }
+                println!(
+                    "\n{} of {} reports are safe removing at most {} {}",
+                    rows.iter()
+                        .filter(|x| x.is_safe_removing(tolerance))
+                        .count(),
+                    rows.len(),
+                    tolerance,
+                    if tolerance == 1 { "level" } else { "levels" }
+                );
+
+                Ok(())
+            }
+            Command::Explain { day, source } => {
+                if day != 2 {
+                    return Err(format!("day {} has no explanations", day).into());
+                }
+
+                let rows = Day2::parse(&source.read(day)?).map_err(describe)?;
+                for row in &rows {
+                    print!("{}", Diagnosis(row));
+                }
+
+                Ok(())
+            }
+            Command::Verify { target, budget } => {
+                let answers = Answers::load(&answers::default_path())?;
+
+                let mut checks = vec![];
+                for day in target.days()? {
+                    let results = Source::default()
+                        .read(day.day)
+                        .map_err(|x| x.to_string())
+                        .and_then(|contents| {
+                            day.run_with_budget(&contents, &Part::ALL, budget)
+                                .map_err(|x| x.to_string())
+                        });
+
+                    match results {
+                        Ok(results) => checks.extend(results.iter().map(|x| Check {
+                            day: x.day,
+                            part: x.part,
+                            answer: x.answer.clone(),
+                            verdict: if x.timed_out {
+                                Verdict::Error(format!("timed out after {:.2?}", x.elapsed))
+                            } else {
+                                answers.verify(x)
+                            },
+                        })),
+                        Err(error) => checks.extend(Part::ALL.map(|part| Check {
+                            day: day.day,
+                            part,
+                            answer: None,
+                            verdict: Verdict::Error(error.clone()),
+                        })),
+                    }
+                }
+
+                print!("{}", Report(&checks));
+
+                if checks.iter().any(|x| x.verdict.is_failure()) {
+                    return Err("verification failed".into());
+                }
+
+                Ok(())
+            }
+            Command::New { day } => {
+                for path in scaffold::new_day(day)? {
+                    println!("created {}", path.display());
+                }
+                println!("registered day {} in src/days/mod.rs", day);
+
+                Ok(())
+            }
+            Command::Fetch { day } => {
+                let config = Config::load()?;
+
+                match client::fetch_input(&config, day, &input::download_dir())? {
+                    Fetched::Cached(path) => println!("using cached {}", path.display()),
+                    Fetched::Downloaded(path) => println!("downloaded {}", path.display()),
+                }
+
+                Ok(())
+            }
+            Command::Submit { day, part } => {
+                let day = Target::Day(day).days()?[0];
+                let contents = Source::default().read(day.day)?;
+                let result = day.run(&contents, &[part])?.remove(0);
+                let answer = result
+                    .answer
+                    .ok_or_else(|| format!("day {} part {} is not solved yet", day.day, part))?;
+
+                let config = Config::load()?;
+                let answers_path = answers::default_path();
+                let history_path = submit::default_history_path();
+                let mut answers = Answers::load(&answers_path)?;
+                let mut history = History::load(&history_path)?;
+
+                let entry = Entry {
+                    day: day.day,
+                    part,
+                    answer: answer.clone(),
+                    input_hash: Some(result.input_hash),
+                };
+                let outcome = submit::submit(&config, &mut answers, &mut history, entry)?;
+
+                history.save(&history_path)?;
+                if outcome == Outcome::Correct {
+                    answers.save(&answers_path)?;
+                }
+
+                println!(
+                    "day {} part {}, answer {}: {}",
+                    day.day, part, answer, outcome
+                );
+
+                Ok(())
+            }
+        }
+    }
+}
+
+impl Target {
+    fn parse(value: &str) -> Result<Self, String> {
+        match value {
+            "all" => Ok(Self::All),
+            day => Ok(Self::Day(parse_day(day)?)),
+        }
+    }
+
+    fn days(&self) -> Result<Vec<&'static Day>, String> {
+        match self {
+            Target::All => Ok(days::REGISTRY.iter().collect()),
+            Target::Day(day) => {
+                Ok(vec![days::find(*day).ok_or_else(|| {
+                    format!("day {} is not implemented", day)
+                })?])
+            }
+        }
+    }
+}
+
+fn parse_day(value: &str) -> Result<u8, String> {
+    match value.parse() {
+        Ok(day) if (1..=25).contains(&day) => Ok(day),
+        _ => Err(format!("invalid day `{}`, expected 1-25 or `all`", value)),
+    }
+}
+
+/// Fails if any part ran out of its time budget, after the results have
+/// been printed.
+fn timeouts(results: &[PartResult]) -> Result<(), Box<dyn Error>> {
+    match results.iter().filter(|x| x.timed_out).count() {
+        0 => Ok(()),
+        1 => Err("1 part timed out".into()),
+        count => Err(format!("{} parts timed out", count).into()),
+    }
+}
+
+/// The message of a parse error followed by the excerpt pointing at it.
+fn describe(error: ParseError) -> String {
+    match error.excerpt() {
+        Some(excerpt) => format!("{}\n{}", error, excerpt),
+        None => error.to_string(),
+    }
+}
+
+fn parse_seconds(value: &str) -> Result<Duration, String> {
+    value
+        .parse()
+        .ok()
+        .and_then(|x| Duration::try_from_secs_f64(x).ok())
+        .ok_or_else(|| format!("invalid number of seconds `{}`", value))
+}
+
+/// Removes `--threads <n>` from anywhere in `args`, since it applies to
+/// every command.
+fn take_threads(args: &mut Vec<String>) -> Result<Option<usize>, String> {
+    let Some(i) = args.iter().position(|x| x == "--threads") else {
+        return Ok(None);
+    };
+
+    let value = args
+        .get(i + 1)
+        .ok_or("missing value for `--threads`")?
+        .clone();
+    args.drain(i..=i + 1);
+
+    match value.parse() {
+        Ok(threads) if threads > 0 => Ok(Some(threads)),
+        _ => Err(format!(
+            "invalid number of threads `{}`, expected at least 1",
+            value
+        )),
+    }
+}
+
+fn main() -> ExitCode {
+    let mut args: Vec<_> = env::args().skip(1).collect();
+    let parsed = take_threads(&mut args)
+        .and_then(|threads| Ok((threads, Command::parse(args.into_iter())?)));
+
+    let command = match parsed {
+        Ok((threads, command)) => {
+            if let Some(threads) = threads {
+                if let Err(error) = parallel::set_threads(threads) {
+                    eprintln!("error: {}", error);
+                    return ExitCode::FAILURE;
+                }
+            }
+            command
+        }
+        Err(error) => {
+            eprintln!("error: {}\n\n{}", error, USAGE);
+            return ExitCode::from(2);
+        }
+    };
+
+    match command.execute() {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("error: {}", error);
+            if let Some(excerpt) = error
+                .downcast_ref::<ParseError>()
+                .and_then(ParseError::excerpt)
+            {
+                eprintln!("{}", excerpt);
+            }
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,195 @@
+//! `aoc new`: generates the files for a new day and adds it to the registry.
+
+use std::{
+    error::Error,
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::{Path, PathBuf},
+};
+
+use advent_of_code_2024::input;
+
+const TEMPLATE: &str = r#"use crate::{ParseError, Solution, Unsolved};
+
+pub struct Day{day};
+
+impl Solution for Day{day} {
+    const DAY: u8 = {day};
+
+    type Input = Vec<String>;
+    type Part1 = Unsolved;
+    type Part2 = Unsolved;
+
+    fn parse(contents: &str) -> Result<Self::Input, ParseError> {
+        Ok(contents.trim().lines().map(String::from).collect())
+    }
+
+    fn part1(_: &Self::Input) -> Self::Part1 {
+        Unsolved
+    }
+
+    fn part2(_: &Self::Input) -> Self::Part2 {
+        Unsolved
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../inputs/day{day}.example.txt");
+
+    #[test]
+    #[ignore = "no example answer yet"]
+    fn part1_example() {
+        let input = Day{day}::parse(EXAMPLE).unwrap();
+        assert_eq!(Day{day}::part1(&input).to_string(), "");
+    }
+
+    #[test]
+    #[ignore = "no example answer yet"]
+    fn part2_example() {
+        let input = Day{day}::parse(EXAMPLE).unwrap();
+        assert_eq!(Day{day}::part2(&input).to_string(), "");
+    }
+}
+"#;
+
+/// Creates the module, an empty input and example for `day`, and registers
+/// it. Nothing is written if the day already has a module.
+pub fn new_day(day: u8) -> Result<Vec<PathBuf>, Box<dyn Error>> {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let module = root.join(format!("src/days/day{}.rs", day));
+    let registry = root.join("src/days/mod.rs");
+
+    if module.exists() {
+        return Err(format!("{} already exists", module.display()).into());
+    }
+    let registered = register(&fs::read_to_string(&registry)?, day)?;
+
+    create(&module, &TEMPLATE.replace("{day}", &day.to_string()))?;
+    let mut created = vec![module];
+
+    for variant in [None, Some("example")] {
+        let path = root.join("src/inputs").join(input::file_name(day, variant));
+        if !path.exists() {
+            create(&path, "")?;
+            created.push(path);
+        }
+    }
+
+    fs::write(&registry, registered)?;
+
+    Ok(created)
+}
+
+/// Writes a file that must not exist yet.
+fn create(path: &Path, contents: &str) -> io::Result<()> {
+    OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(path)?
+        .write_all(contents.as_bytes())
+}
+
+/// Adds `day` to the source of `days/mod.rs`, keeping the module
+/// declarations in rustfmt's order and the registry in day order.
+fn register(source: &str, day: u8) -> Result<String, String> {
+    let declaration = format!("pub mod day{};", day);
+    let mut lines: Vec<_> = source.lines().map(String::from).collect();
+
+    if lines.iter().any(|x| x.trim() == declaration) {
+        return Err(format!("day {} is already registered", day));
+    }
+
+    let entry_day = |line: &str| -> Option<u8> {
+        line.trim()
+            .strip_prefix("Day::new::<day")?
+            .split_once(':')?
+            .0
+            .parse()
+            .ok()
+    };
+
+    let entries: Vec<_> = lines
+        .iter()
+        .enumerate()
+        .filter_map(|(i, x)| Some((i, entry_day(x)?)))
+        .collect();
+    let position = match entries.iter().find(|(_, x)| *x > day) {
+        Some((i, _)) => *i,
+        None => match entries.last() {
+            Some((i, _)) => i + 1,
+            None => {
+                lines
+                    .iter()
+                    .position(|x| x.trim_end().ends_with("&[Day] = &["))
+                    .ok_or("could not find the registry in days/mod.rs")?
+                    + 1
+            }
+        },
+    };
+    lines.insert(
+        position,
+        format!("    Day::new::<day{}::Day{}>(),", day, day),
+    );
+
+    let modules = lines
+        .iter()
+        .position(|x| x.starts_with("pub mod "))
+        .unwrap_or(0);
+    let end = lines[modules..]
+        .iter()
+        .position(|x| !x.starts_with("pub mod "))
+        .map_or(lines.len(), |x| modules + x);
+    lines.insert(end, declaration);
+    lines[modules..=end].sort_by_key(|x| x.trim_end_matches(';').to_string());
+
+    Ok(lines.join("\n") + "\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const REGISTRY: &str = "\
+pub mod day1;
+pub mod day15;
+pub mod day2;
+
+use crate::runner::Day;
+
+pub static REGISTRY: &[Day] = &[
+    Day::new::<day1::Day1>(),
+    Day::new::<day2::Day2>(),
+    Day::new::<day15::Day15>(),
+];
+";
+
+    #[test]
+    fn register_in_order() {
+        assert_eq!(
+            register(REGISTRY, 12).unwrap(),
+            "\
+pub mod day1;
+pub mod day12;
+pub mod day15;
+pub mod day2;
+
+use crate::runner::Day;
+
+pub static REGISTRY: &[Day] = &[
+    Day::new::<day1::Day1>(),
+    Day::new::<day2::Day2>(),
+    Day::new::<day12::Day12>(),
+    Day::new::<day15::Day15>(),
+];
+"
+        );
+    }
+
+    #[test]
+    fn register_twice() {
+        assert!(register(REGISTRY, 15).is_err());
+    }
+}
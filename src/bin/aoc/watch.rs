@@ -0,0 +1,121 @@
+//! `aoc watch`: re-runs a day whenever its source or input changes.
+
+use std::{
+    env,
+    error::Error,
+    path::Path,
+    process::{Command, Stdio},
+    thread,
+    time::Duration,
+};
+
+use advent_of_code_2024::{
+    input::Source,
+    parallel,
+    runner::{self, Part, PartResult},
+    watch::{Diff, Watcher},
+};
+
+/// Polls the module and input of `day` every `interval`, running it once at
+/// the start and again after each change until interrupted. A day split into
+/// submodules under `src/days/dayN/` has that directory watched as well.
+///
+/// Edits to the source only take effect after a rebuild, so every run goes
+/// through `cargo run` rather than calling the day directly. The build uses
+/// its own target directory so it never has to replace this executable while
+/// it is running.
+pub fn watch(
+    day: u8,
+    parts: &[Part],
+    source: &Source,
+    interval: Duration,
+) -> Result<(), Box<dyn Error>> {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let module = root.join(format!("src/days/day{}.rs", day));
+    let dir = root.join(format!("src/days/day{}", day));
+    if !module.exists() && !dir.join("mod.rs").exists() {
+        return Err(format!("day {} is not implemented", day).into());
+    }
+
+    let sources = [module, dir].into_iter().filter(|x| x.exists());
+    let mut watcher = Watcher::new(sources.chain(source.paths(day)));
+    let mut previous: Option<Vec<PartResult>> = None;
+
+    println!("watching day {}, press Ctrl-C to stop", day);
+    let mut changed = vec![];
+    loop {
+        if !changed.is_empty() {
+            println!("\nchanged: {}", changed.join(", "));
+        }
+
+        match run(root, day, parts, source) {
+            Ok(results) => {
+                print!(
+                    "{}",
+                    Diff {
+                        previous: previous.as_deref(),
+                        current: &results,
+                    }
+                );
+                previous = Some(results);
+            }
+            Err(error) => println!("error: {}", error),
+        }
+
+        changed = loop {
+            thread::sleep(interval);
+            let changed = watcher.poll();
+            if !changed.is_empty() {
+                break changed.iter().map(|x| x.display().to_string()).collect();
+            }
+        };
+    }
+}
+
+/// Builds and runs the day in a child process, reading its results back from
+/// CSV. Compiler and parse errors go straight to the terminal.
+fn run(
+    root: &Path,
+    day: u8,
+    parts: &[Part],
+    source: &Source,
+) -> Result<Vec<PartResult>, Box<dyn Error>> {
+    let cargo = env::var_os("CARGO").unwrap_or_else(|| "cargo".into());
+
+    let mut command = Command::new(cargo);
+    command
+        .current_dir(root)
+        .args(["run", "--quiet", "--bin", "aoc", "--target-dir"])
+        .arg(root.join("target/watch"));
+    if !cfg!(debug_assertions) {
+        command.arg("--release");
+    }
+    // Built with the same features as this executable
+    if cfg!(feature = "count-alloc") {
+        command.args(["--features", "count-alloc"]);
+    }
+    if cfg!(feature = "sequential") {
+        command.args(["--features", "sequential"]);
+    }
+    command.args(["--", "run", &day.to_string(), "--format", "csv"]);
+    command.args(["--threads", &parallel::threads().to_string()]);
+    if let [part] = parts {
+        command.args(["--part", &part.to_string()]);
+    }
+    match source {
+        Source::Named(Some(variant)) => {
+            command.args(["--variant", variant]);
+        }
+        Source::Path(path) => {
+            command.arg("--input").arg(path);
+        }
+        Source::Named(None) | Source::Stdin => {}
+    }
+
+    let output = command.stderr(Stdio::inherit()).output()?;
+    if !output.status.success() {
+        return Err(format!("run failed with {}", output.status).into());
+    }
+
+    Ok(runner::parse_csv(&String::from_utf8(output.stdout)?)?)
+}
@@ -0,0 +1,152 @@
+//! Cooperative cancellation, so a part that runs past its time budget can
+//! stop early instead of hanging the runner.
+//!
+//! The runner makes a token current on the thread running a part. Solvers
+//! that loop for long take it with `Token::current` and stop once it is
+//! cancelled, returning whatever they have, which the runner then discards.
+//! Parallel loops take it before fanning out, since worker threads have no
+//! current token of their own.
+
+use std::{
+    cell::RefCell,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc, Arc,
+    },
+    thread::{self, JoinHandle},
+    time::Duration,
+};
+
+thread_local! {
+    static CURRENT: RefCell<Option<Token>> = const { RefCell::new(None) };
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Token(Arc<AtomicBool>);
+
+impl Token {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    /// Cheap enough to check on every iteration of a loop.
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+
+    /// The token of the part running on this thread, or one that is never
+    /// cancelled outside of a part.
+    pub fn current() -> Self {
+        CURRENT.with(|x| x.borrow().clone()).unwrap_or_default()
+    }
+
+    /// Runs `func` with this as the current token, putting the previous one
+    /// back afterwards even if `func` panics.
+    pub fn scope<T>(&self, func: impl FnOnce() -> T) -> T {
+        let _restore = Restore(CURRENT.with(|x| x.replace(Some(self.clone()))));
+        func()
+    }
+}
+
+/// Makes the token it holds current again when dropped.
+struct Restore(Option<Token>);
+
+impl Drop for Restore {
+    fn drop(&mut self) {
+        let previous = self.0.take();
+        CURRENT.with(|x| x.replace(previous));
+    }
+}
+
+/// Cancels a token once a budget has passed, unless dropped first.
+pub struct Deadline {
+    done: Option<mpsc::Sender<()>>,
+    timer: Option<JoinHandle<()>>,
+}
+
+impl Deadline {
+    pub fn start(token: Token, budget: Duration) -> Self {
+        let (done, finished) = mpsc::channel::<()>();
+
+        let timer = thread::spawn(move || {
+            // Dropping the sender wakes this up early with an error other
+            // than a timeout
+            if let Err(mpsc::RecvTimeoutError::Timeout) = finished.recv_timeout(budget) {
+                token.cancel();
+            }
+        });
+
+        Self {
+            done: Some(done),
+            timer: Some(timer),
+        }
+    }
+}
+
+impl Drop for Deadline {
+    fn drop(&mut self) {
+        drop(self.done.take());
+        if let Some(timer) = self.timer.take() {
+            let _ = timer.join();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{panic, time::Instant};
+
+    use super::*;
+
+    #[test]
+    fn deadline() {
+        let token = Token::new();
+        let _deadline = Deadline::start(token.clone(), Duration::from_millis(20));
+
+        let start = Instant::now();
+        let spins = token.scope(|| {
+            let current = Token::current();
+            let mut spins = 0_u64;
+            while !current.is_cancelled() {
+                spins += 1;
+            }
+            spins
+        });
+
+        assert!(spins > 0);
+        assert!(start.elapsed() >= Duration::from_millis(20));
+        assert!(!Token::current().is_cancelled());
+    }
+
+    #[test]
+    fn scope_restores_after_panic() {
+        let outer = Token::new();
+        let inner = Token::new();
+        inner.cancel();
+
+        outer.scope(|| {
+            let result = panic::catch_unwind(|| {
+                inner.scope(|| panic!("part failed"));
+            });
+            assert!(result.is_err());
+            assert!(!Token::current().is_cancelled());
+
+            outer.cancel();
+            assert!(Token::current().is_cancelled());
+        });
+
+        assert!(!Token::current().is_cancelled());
+    }
+
+    #[test]
+    fn finish_early() {
+        let token = Token::new();
+        drop(Deadline::start(token.clone(), Duration::from_secs(60)));
+
+        assert!(!token.is_cancelled());
+    }
+}
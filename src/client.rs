@@ -0,0 +1,290 @@
+//! HTTP access to the Advent of Code site.
+
+use std::{
+    error::Error,
+    fmt::{self, Display},
+    fs, io,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use crate::{config::Config, input, runner::Part};
+
+pub const YEAR: u16 = 2024;
+
+pub struct Client {
+    agent: ureq::Agent,
+    base_url: String,
+    session: String,
+}
+
+impl Client {
+    pub fn new(config: &Config) -> Result<Self, ClientError> {
+        let session = config
+            .session
+            .as_deref()
+            .map(str::trim)
+            .filter(|x| !x.is_empty())
+            .ok_or(ClientError::MissingSession)?;
+
+        let agent = ureq::AgentBuilder::new()
+            .user_agent(&user_agent(config.contact.as_deref()))
+            .timeout(Duration::from_secs(30))
+            .build();
+
+        Ok(Self {
+            agent,
+            base_url: config.base_url().to_string(),
+            session: session.to_string(),
+        })
+    }
+
+    pub fn input(&self, day: u8) -> Result<String, ClientError> {
+        let url = format!("{}/{}/day/{}/input", self.base_url, YEAR, day);
+
+        read(&url, self.request("GET", &url).call())
+    }
+
+    /// Posts an answer and returns the page that comes back.
+    pub fn submit(&self, day: u8, part: Part, answer: &str) -> Result<String, ClientError> {
+        let url = format!("{}/{}/day/{}/answer", self.base_url, YEAR, day);
+        let level = u8::from(part).to_string();
+
+        read(
+            &url,
+            self.request("POST", &url)
+                .send_form(&[("level", &level), ("answer", answer)]),
+        )
+    }
+
+    fn request(&self, method: &str, url: &str) -> ureq::Request {
+        self.agent
+            .request(method, url)
+            .set("Cookie", &format!("session={}", self.session))
+    }
+}
+
+/// Reads the body of a response, turning error statuses into errors.
+fn read(url: &str, response: Result<ureq::Response, ureq::Error>) -> Result<String, ClientError> {
+    let response = response.map_err(|error| match error {
+        ureq::Error::Status(status, response) => ClientError::Status {
+            url: url.to_string(),
+            status,
+            body: response.into_string().unwrap_or_default(),
+        },
+        ureq::Error::Transport(error) => ClientError::Transport {
+            url: url.to_string(),
+            message: error.to_string(),
+        },
+    })?;
+
+    response
+        .into_string()
+        .map_err(|error| ClientError::Transport {
+            url: url.to_string(),
+            message: error.to_string(),
+        })
+}
+
+/// Names this tool and, if given, how to contact whoever runs it.
+pub fn user_agent(contact: Option<&str>) -> String {
+    let name = concat!(env!("CARGO_PKG_NAME"), "/", env!("CARGO_PKG_VERSION"));
+
+    match contact {
+        Some(contact) => format!("{} ({})", name, contact),
+        None => name.to_string(),
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Fetched {
+    Cached(PathBuf),
+    Downloaded(PathBuf),
+}
+
+/// Downloads the input for `day` into `dir`, unless a non-empty copy is
+/// already there. No session is needed when the input is cached.
+pub fn fetch_input(config: &Config, day: u8, dir: &Path) -> Result<Fetched, ClientError> {
+    let path = dir.join(input::file_name(day, None));
+    if fs::metadata(&path).is_ok_and(|x| x.len() > 0) {
+        return Ok(Fetched::Cached(path));
+    }
+
+    let contents = Client::new(config)?.input(day)?;
+
+    // Written next to the target and renamed, so an interrupted download is
+    // never mistaken for a cached input
+    let partial = path.with_extension("txt.part");
+    let io_error = |error| ClientError::Io {
+        path: partial.clone(),
+        error,
+    };
+    fs::create_dir_all(dir).map_err(io_error)?;
+    fs::write(&partial, contents).map_err(io_error)?;
+    fs::rename(&partial, &path).map_err(io_error)?;
+
+    Ok(Fetched::Downloaded(path))
+}
+
+#[derive(Debug)]
+pub enum ClientError {
+    MissingSession,
+    Status {
+        url: String,
+        status: u16,
+        body: String,
+    },
+    /// The request did not get a response at all.
+    Transport {
+        url: String,
+        message: String,
+    },
+    Io {
+        path: PathBuf,
+        error: io::Error,
+    },
+}
+
+impl Display for ClientError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ClientError::MissingSession => write!(
+                f,
+                "no session token, set ${} or `session` in {}",
+                crate::config::SESSION_VAR,
+                crate::config::CONFIG_FILE
+            ),
+            ClientError::Status { url, status, body } => {
+                write!(f, "{} returned status {}", url, status)?;
+                match body.lines().map(str::trim).find(|x| !x.is_empty()) {
+                    Some(line) => write!(f, ": {}", line),
+                    None => Ok(()),
+                }
+            }
+            ClientError::Transport { url, message } => {
+                write!(f, "request to {} failed: {}", url, message)
+            }
+            ClientError::Io { path, error } => {
+                write!(f, "failed to write {}: {}", path.display(), error)
+            }
+        }
+    }
+}
+
+impl Error for ClientError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ClientError::Io { error, .. } => Some(error),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use std::{
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpListener,
+        process,
+        thread::{self, JoinHandle},
+    };
+
+    use super::*;
+
+    /// Answers a single request with `status` and `body` and returns the
+    /// request it received.
+    pub(crate) fn serve_once(
+        status: &'static str,
+        body: &'static str,
+    ) -> (String, JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+
+        let handle = thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream);
+
+            let mut request = String::new();
+            let mut length = 0;
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if let Some((name, value)) = line.split_once(':') {
+                    if name.eq_ignore_ascii_case("content-length") {
+                        length = value.trim().parse().unwrap();
+                    }
+                }
+                request.push_str(&line);
+                if line == "\r\n" {
+                    break;
+                }
+            }
+
+            let mut content = vec![0; length];
+            reader.read_exact(&mut content).unwrap();
+            request.push_str(&String::from_utf8(content).unwrap());
+
+            write!(
+                reader.get_mut(),
+                "HTTP/1.1 {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                status,
+                body.len(),
+                body
+            )
+            .unwrap();
+
+            request
+        });
+
+        (url, handle)
+    }
+
+    fn config(base_url: String) -> Config {
+        Config {
+            session: Some(String::from("abc123")),
+            base_url: Some(base_url),
+            contact: Some(String::from("someone@example.com")),
+        }
+    }
+
+    #[test]
+    fn fetch_then_cached() {
+        let dir = std::env::temp_dir().join(format!("aoc-fetch-{}", process::id()));
+        let (url, server) = serve_once("200 OK", "1 2\n3 4\n");
+
+        let fetched = fetch_input(&config(url), 3, &dir).unwrap();
+        let request = server.join().unwrap().to_lowercase();
+        let path = dir.join("day3.txt");
+
+        assert_eq!(fetched, Fetched::Downloaded(path.clone()));
+        assert_eq!(fs::read_to_string(&path).unwrap(), "1 2\n3 4\n");
+        assert!(request.starts_with("get /2024/day/3/input "));
+        assert!(request.contains("cookie: session=abc123"));
+        assert!(request.contains("user-agent: advent-of-code-2024/"));
+        assert!(request.contains("someone@example.com"));
+
+        // Nothing is listening any more, so this only passes from the cache
+        let config = config(String::from("http://127.0.0.1:1"));
+        assert_eq!(
+            fetch_input(&config, 3, &dir).unwrap(),
+            Fetched::Cached(path)
+        );
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn fetch_error_status() {
+        let dir = std::env::temp_dir().join(format!("aoc-fetch-error-{}", process::id()));
+        let (url, server) = serve_once("400 Bad Request", "Please log in.\n");
+
+        let error = fetch_input(&config(url), 3, &dir).unwrap_err();
+        server.join().unwrap();
+
+        assert!(matches!(error, ClientError::Status { status: 400, .. }));
+        assert!(error
+            .to_string()
+            .ends_with("returned status 400: Please log in."));
+        assert!(!dir.join("day3.txt").exists());
+    }
+}
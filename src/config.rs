@@ -0,0 +1,116 @@
+//! Settings for talking to the Advent of Code site, read from `aoc.toml` and
+//! overridden by environment variables.
+
+use std::{
+    env,
+    error::Error,
+    fmt::{self, Display},
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use serde::Deserialize;
+
+pub const CONFIG_FILE: &str = "aoc.toml";
+
+/// Environment variable naming a config file to use instead of `aoc.toml`.
+pub const CONFIG_VAR: &str = "AOC_CONFIG";
+pub const SESSION_VAR: &str = "AOC_SESSION";
+pub const BASE_URL_VAR: &str = "AOC_BASE_URL";
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Config {
+    /// Value of the `session` cookie of a logged in browser.
+    pub session: Option<String>,
+    /// Site to talk to, which can point at a local stand-in for testing.
+    pub base_url: Option<String>,
+    /// How to reach whoever runs this, added to the User-Agent as the site
+    /// asks automated tools to do.
+    pub contact: Option<String>,
+}
+
+impl Config {
+    /// Reads the config file if there is one, then applies `$AOC_SESSION`
+    /// and `$AOC_BASE_URL` on top.
+    pub fn load() -> Result<Self, ConfigError> {
+        let path = match env::var_os(CONFIG_VAR) {
+            Some(path) => PathBuf::from(path),
+            None => crate::project_file(CONFIG_FILE),
+        };
+
+        let mut config = Self::from_file(&path)?;
+
+        if let Ok(session) = env::var(SESSION_VAR) {
+            config.session = Some(session);
+        }
+        if let Ok(base_url) = env::var(BASE_URL_VAR) {
+            config.base_url = Some(base_url);
+        }
+
+        Ok(config)
+    }
+
+    /// Reads a config file, where a missing file is the same as an empty one.
+    pub fn from_file(path: &Path) -> Result<Self, ConfigError> {
+        let contents = match fs::read_to_string(path) {
+            Ok(contents) => contents,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(Self::default()),
+            Err(error) => {
+                return Err(ConfigError::Io {
+                    path: path.to_path_buf(),
+                    error,
+                })
+            }
+        };
+
+        toml::from_str(&contents).map_err(|error| ConfigError::Parse {
+            path: path.to_path_buf(),
+            error,
+        })
+    }
+
+    /// The base URL without a trailing slash.
+    pub fn base_url(&self) -> &str {
+        self.base_url
+            .as_deref()
+            .unwrap_or(DEFAULT_BASE_URL)
+            .trim_end_matches('/')
+    }
+}
+
+#[derive(Debug)]
+pub enum ConfigError {
+    Io {
+        path: PathBuf,
+        error: io::Error,
+    },
+    Parse {
+        path: PathBuf,
+        error: toml::de::Error,
+    },
+}
+
+impl Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigError::Io { path, error } => {
+                write!(f, "failed to read {}: {}", path.display(), error)
+            }
+            ConfigError::Parse { path, error } => {
+                write!(f, "invalid config file {}: {}", path.display(), error)
+            }
+        }
+    }
+}
+
+impl Error for ConfigError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ConfigError::Io { error, .. } => Some(error),
+            ConfigError::Parse { error, .. } => Some(error),
+        }
+    }
+}
@@ -0,0 +1,294 @@
+pub mod external;
+pub mod online;
+
+use crate::{generate::Rng, parse, ParseError, Solution};
+
+pub struct Day1;
+
+#[derive(Debug)]
+struct Pair(u32, u32);
+
+impl Pair {
+    pub fn new(left: u32, right: u32) -> Self {
+        Self(left, right)
+    }
+
+    fn distance(&self) -> u32 {
+        self.0.abs_diff(self.1)
+    }
+
+    fn total_distance(pairs: Vec<Self>) -> u32 {
+        pairs.iter().map(|x| x.distance()).sum()
+    }
+}
+
+#[derive(Debug)]
+pub struct SortedInput(Vec<u32>, Vec<u32>);
+
+impl SortedInput {
+    pub fn parse(contents: &str) -> Result<Self, ParseError> {
+        let mut left: Vec<u32> = vec![];
+        let mut right: Vec<u32> = vec![];
+        for line in contents.trim().lines() {
+            let (x, y) = Self::parse_line(contents, line)?;
+            left.push(x);
+            right.push(y);
+        }
+
+        Ok(Self::new(left, right))
+    }
+
+    /// Parses one line of `contents` into its left and right location IDs.
+    fn parse_line(contents: &str, line: &str) -> Result<(u32, u32), ParseError> {
+        let items: Vec<_> = line.split(' ').filter(|&x| !x.is_empty()).collect();
+        if items.len() != 2 {
+            return Err(ParseError::new(
+                Day1::DAY,
+                contents,
+                line,
+                "two location IDs separated by spaces",
+            ));
+        }
+
+        Ok((
+            parse::number(Day1::DAY, contents, items[0], "a location ID")?,
+            parse::number(Day1::DAY, contents, items[1], "a location ID")?,
+        ))
+    }
+
+    pub fn new(mut left: Vec<u32>, mut right: Vec<u32>) -> Self {
+        assert_eq!(left.len(), right.len());
+
+        left.sort();
+        right.sort();
+
+        Self(left, right)
+    }
+
+    fn get_pairs(&self) -> Vec<Pair> {
+        assert_eq!(self.0.len(), self.1.len());
+
+        self.0
+            .iter()
+            .zip(&self.1)
+            .map(|(left, right)| Pair::new(*left, *right))
+            .collect()
+    }
+
+    pub fn similarity_score(&self) -> u32 {
+        let mut score_total = 0;
+        let mut right_i = 0;
+
+        for x in self.0.iter() {
+            let (score, i) = Self::score_item(x, &self.1[right_i..]);
+
+            score_total += x * score;
+            right_i += i as usize;
+        }
+
+        score_total
+    }
+
+    /// Counts occurrences of `value` in `right`, and how many smaller items
+    /// can be skipped. Equal items are kept for repeats on the left.
+    fn score_item(value: &u32, right: &[u32]) -> (u32, u32) {
+        let mut counter = 0;
+        let mut i = 0;
+
+        for x in right.iter() {
+            if x > value {
+                break;
+            } else if x == value {
+                counter += 1;
+            } else {
+                i += 1;
+            }
+        }
+
+        (counter, i)
+    }
+
+    /// Similarity score where right IDs within `tolerance` of a left ID count
+    /// as matches too, each weighted by `weighting`. A tolerance of 0 with
+    /// either weighting gives `similarity_score`.
+    ///
+    /// Both lists are sorted, so the right IDs near each left ID form a
+    /// window that only moves up as the left ID does. The window is split at
+    /// the left ID itself, and the count and sum of each half are kept as the
+    /// three pointers move, which gives the weighted total for a window in
+    /// constant time.
+    pub fn tolerant_similarity_score(&self, tolerance: u32, weighting: Weighting) -> u64 {
+        let right = &self.1;
+        let width = tolerance as u64 + 1;
+
+        // `right[start..middle]` is below the left ID and `right[middle..end]`
+        // at or above it, all within the tolerance
+        let (mut start, mut middle, mut end) = (0, 0, 0);
+        let (mut below, mut above) = (0_u64, 0_u64);
+        let mut score_total = 0;
+
+        for &x in &self.0 {
+            while end < right.len() && right[end] <= x.saturating_add(tolerance) {
+                above += right[end] as u64;
+                end += 1;
+            }
+            while middle < end && right[middle] < x {
+                above -= right[middle] as u64;
+                below += right[middle] as u64;
+                middle += 1;
+            }
+            while start < middle && right[start] < x.saturating_sub(tolerance) {
+                below -= right[start] as u64;
+                start += 1;
+            }
+
+            let (count_below, count_above) = ((middle - start) as u64, (end - middle) as u64);
+            let score = match weighting {
+                Weighting::Flat => count_below + count_above,
+                Weighting::Linear => {
+                    let distance_below = count_below * x as u64 - below;
+                    let distance_above = above - count_above * x as u64;
+                    (count_below + count_above) * width - distance_below - distance_above
+                }
+            };
+
+            score_total += x as u64 * score;
+        }
+
+        score_total
+    }
+}
+
+/// How much a right ID within the tolerance of a left ID counts towards the
+/// similarity score.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Weighting {
+    /// Every ID within the tolerance counts once.
+    #[default]
+    Flat,
+    /// An ID `d` away from the left ID counts `tolerance + 1 - d` times, so
+    /// exact matches count most.
+    Linear,
+}
+
+impl Solution for Day1 {
+    const DAY: u8 = 1;
+
+    type Input = SortedInput;
+    type Part1 = u32;
+    type Part2 = u32;
+
+    fn parse(contents: &str) -> Result<Self::Input, ParseError> {
+        SortedInput::parse(contents)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Part1 {
+        Pair::total_distance(input.get_pairs())
+    }
+
+    fn part2(input: &Self::Input) -> Self::Part2 {
+        input.similarity_score()
+    }
+
+    /// `size` pairs of five digit location IDs, where about a quarter of the
+    /// left list and a third of the right repeat IDs from the left.
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        let mut left = vec![];
+        for _ in 0..size {
+            let id = if !left.is_empty() && rng.one_in(4) {
+                *rng.pick(&left)
+            } else {
+                rng.range(10000..=99999)
+            };
+            left.push(id);
+        }
+
+        let mut contents = String::new();
+        for x in &left {
+            let right = if rng.one_in(3) {
+                *rng.pick(&left)
+            } else {
+                rng.range(10000..=99999)
+            };
+            contents.push_str(&format!("{}   {}\n", x, right));
+        }
+
+        Some(contents)
+    }
+}
+
+/// Similarity score counting every left ID's occurrences in the whole right
+/// list, see `differential`.
+pub(crate) fn brute_force_part2(contents: &str) -> String {
+    let (left, right): (Vec<u64>, Vec<u64>) = contents
+        .lines()
+        .map(|x| {
+            let mut ids = x.split_whitespace().map(|y| y.parse::<u64>().unwrap());
+            (ids.next().unwrap(), ids.next().unwrap())
+        })
+        .unzip();
+
+    left.iter()
+        .map(|x| x * right.iter().filter(|&y| y == x).count() as u64)
+        .sum::<u64>()
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../inputs/day1.example.txt");
+
+    #[test]
+    fn tolerant_example() {
+        let input = Day1::parse(EXAMPLE).unwrap();
+
+        assert_eq!(input.tolerant_similarity_score(0, Weighting::Flat), 31);
+        assert_eq!(input.tolerant_similarity_score(0, Weighting::Linear), 31);
+        assert_eq!(input.tolerant_similarity_score(1, Weighting::Flat), 62);
+        assert_eq!(input.tolerant_similarity_score(1, Weighting::Linear), 93);
+    }
+
+    #[test]
+    fn tolerant_matches_brute_force() {
+        for seed in 0..5 {
+            let mut rng = Rng::new(seed);
+            let left: Vec<u32> = (0..200).map(|_| rng.range(0..=300) as u32).collect();
+            let right: Vec<u32> = (0..200).map(|_| rng.range(0..=300) as u32).collect();
+            let input = SortedInput::new(left.clone(), right.clone());
+
+            for tolerance in [0, 1, 5, 40, 1000] {
+                let brute_force = |weight: &dyn Fn(u32) -> u64| -> u64 {
+                    left.iter()
+                        .map(|&x| {
+                            let near = right.iter().filter(|y| x.abs_diff(**y) <= tolerance);
+                            x as u64 * near.map(|y| weight(x.abs_diff(*y))).sum::<u64>()
+                        })
+                        .sum()
+                };
+
+                assert_eq!(
+                    input.tolerant_similarity_score(tolerance, Weighting::Flat),
+                    brute_force(&|_| 1)
+                );
+                assert_eq!(
+                    input.tolerant_similarity_score(tolerance, Weighting::Linear),
+                    brute_force(&|d| (tolerance + 1 - d) as u64)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn part1_example() {
+        let input = Day1::parse(EXAMPLE).unwrap();
+        assert_eq!(Day1::part1(&input), 11);
+    }
+
+    #[test]
+    fn part2_example() {
+        let input = Day1::parse(EXAMPLE).unwrap();
+        assert_eq!(Day1::part2(&input), 31);
+    }
+}
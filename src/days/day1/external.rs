@@ -0,0 +1,416 @@
+//! Day 1 for inputs too large to hold in memory. The lists are parsed a
+//! chunk at a time, each chunk is sorted and written to a temporary file as
+//! a run, and the runs of each list are merged back into one sorted stream.
+//!
+//! Both answers come out of a single pass over the two streams, stepping
+//! through the distinct IDs in order. The similarity score adds each ID times
+//! its count in the left list and its count in the right. The total distance
+//! between two sorted lists of the same length is the area between their
+//! counting functions, so between one ID and the next it adds the gap times
+//! the difference in how many IDs of each list have been passed.
+
+use std::{
+    cmp::Reverse,
+    collections::BinaryHeap,
+    env,
+    error::Error,
+    fmt::{self, Display},
+    fs::{self, File},
+    io::{self, BufRead, BufReader, BufWriter, Read, Write},
+    path::{Path, PathBuf},
+    process,
+    sync::atomic::{AtomicUsize, Ordering},
+    vec,
+};
+
+use super::SortedInput;
+use crate::ParseError;
+
+#[derive(Debug, Clone)]
+pub struct Options {
+    /// Lines sorted in memory at a time, at 8 bytes a line.
+    pub chunk_lines: usize,
+    /// Most runs merged at once, which bounds the number of open files.
+    /// More runs than this are first merged into longer ones.
+    pub fan_in: usize,
+    /// Where to create the directory holding the runs, which is removed
+    /// afterwards.
+    pub temp_dir: PathBuf,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            chunk_lines: 1 << 22,
+            fan_in: 64,
+            temp_dir: env::temp_dir(),
+        }
+    }
+}
+
+/// Both answers, wide enough for billions of lines.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Totals {
+    pub pairs: u64,
+    /// Runs written to disk for each list.
+    pub runs: usize,
+    pub distance: u128,
+    pub similarity: u128,
+}
+
+/// Computes both answers from `reader` without holding more than a chunk of
+/// it in memory. Blank lines are skipped.
+pub fn solve(mut reader: impl BufRead, options: &Options) -> Result<Totals, ExternalError> {
+    assert!(
+        options.chunk_lines > 0,
+        "chunks must hold at least one line"
+    );
+    assert!(
+        options.fan_in > 1,
+        "merging needs at least two runs at once"
+    );
+
+    let mut dir = RunDir::new(&options.temp_dir);
+    let mut left = Vec::with_capacity(options.chunk_lines);
+    let mut right = Vec::with_capacity(options.chunk_lines);
+    let mut left_runs = vec![];
+    let mut right_runs = vec![];
+    let mut pairs = 0;
+
+    let mut line = String::new();
+    let mut number = 0;
+    loop {
+        line.clear();
+        if reader.read_line(&mut line).map_err(ExternalError::Read)? == 0 {
+            break;
+        }
+        number += 1;
+
+        let text = line.trim_end_matches(['\n', '\r']);
+        if text.trim().is_empty() {
+            continue;
+        }
+
+        let (x, y) = SortedInput::parse_line(text, text).map_err(|mut error| {
+            error.line = number;
+            ExternalError::Parse(error)
+        })?;
+        left.push(x);
+        right.push(y);
+        pairs += 1;
+
+        if left.len() == options.chunk_lines {
+            left_runs.push(dir.spill(&mut left)?);
+            right_runs.push(dir.spill(&mut right)?);
+        }
+    }
+
+    let runs = left_runs.len();
+    let left = dir.merge(left_runs, left, options.fan_in)?;
+    let right = dir.merge(right_runs, right, options.fan_in)?;
+    let (distance, similarity) = sweep(left, right).map_err(|x| dir.error(x))?;
+
+    Ok(Totals {
+        pairs,
+        runs,
+        distance,
+        similarity,
+    })
+}
+
+/// Steps through the distinct IDs of both sorted streams, see the module
+/// docs.
+fn sweep(mut left: Merge, mut right: Merge) -> io::Result<(u128, u128)> {
+    let mut distance = 0;
+    let mut similarity = 0;
+
+    // How many IDs of each list are at most `previous`
+    let mut passed_left = 0_u128;
+    let mut passed_right = 0_u128;
+    let mut previous = None;
+
+    let mut next_left = left.next()?;
+    let mut next_right = right.next()?;
+    while let Some(id) = next_left.into_iter().chain(next_right).min() {
+        if let Some(previous) = previous {
+            distance += passed_left.abs_diff(passed_right) * (id - previous) as u128;
+        }
+
+        let mut count_left = 0;
+        while next_left == Some(id) {
+            count_left += 1;
+            next_left = left.next()?;
+        }
+        let mut count_right = 0;
+        while next_right == Some(id) {
+            count_right += 1;
+            next_right = right.next()?;
+        }
+
+        similarity += id as u128 * count_left * count_right;
+        passed_left += count_left;
+        passed_right += count_right;
+        previous = Some(id);
+    }
+
+    Ok((distance, similarity))
+}
+
+/// A sorted run, either the last chunk still in memory or a file of
+/// little-endian IDs.
+enum Run {
+    Memory(vec::IntoIter<u32>),
+    File(BufReader<File>),
+}
+
+impl Run {
+    fn next(&mut self) -> io::Result<Option<u32>> {
+        match self {
+            Run::Memory(ids) => Ok(ids.next()),
+            Run::File(reader) => {
+                let mut bytes = [0; 4];
+                match reader.read_exact(&mut bytes) {
+                    Ok(()) => Ok(Some(u32::from_le_bytes(bytes))),
+                    Err(error) if error.kind() == io::ErrorKind::UnexpectedEof => Ok(None),
+                    Err(error) => Err(error),
+                }
+            }
+        }
+    }
+}
+
+/// K-way merge of sorted runs into one sorted stream.
+struct Merge {
+    runs: Vec<Run>,
+    /// The next ID of each run that has not run out, with its index.
+    heads: BinaryHeap<Reverse<(u32, usize)>>,
+}
+
+impl Merge {
+    fn new(mut runs: Vec<Run>) -> io::Result<Self> {
+        let mut heads = BinaryHeap::with_capacity(runs.len());
+        for (i, run) in runs.iter_mut().enumerate() {
+            if let Some(id) = run.next()? {
+                heads.push(Reverse((id, i)));
+            }
+        }
+
+        Ok(Self { runs, heads })
+    }
+
+    fn next(&mut self) -> io::Result<Option<u32>> {
+        let Some(Reverse((id, i))) = self.heads.pop() else {
+            return Ok(None);
+        };
+        if let Some(next) = self.runs[i].next()? {
+            self.heads.push(Reverse((next, i)));
+        }
+
+        Ok(Some(id))
+    }
+}
+
+/// Ensures each `RunDir` gets a directory of its own, as tests solve at the
+/// same time.
+static DIRS: AtomicUsize = AtomicUsize::new(0);
+
+/// A temporary directory of run files, created on the first spill and
+/// removed when dropped.
+struct RunDir {
+    path: PathBuf,
+    created: bool,
+    files: usize,
+}
+
+impl RunDir {
+    fn new(parent: &Path) -> Self {
+        let name = format!(
+            "aoc-day1-{}-{}",
+            process::id(),
+            DIRS.fetch_add(1, Ordering::Relaxed)
+        );
+
+        Self {
+            path: parent.join(name),
+            created: false,
+            files: 0,
+        }
+    }
+
+    /// Sorts `ids` into a new run file and empties it for the next chunk.
+    fn spill(&mut self, ids: &mut Vec<u32>) -> Result<PathBuf, ExternalError> {
+        ids.sort_unstable();
+        let mut sorted = ids.iter().copied();
+        let path = self
+            .write(|| Ok(sorted.next()))
+            .map_err(|x| self.error(x))?;
+        ids.clear();
+
+        Ok(path)
+    }
+
+    /// Merges the runs at `paths` and the chunk still in memory into one
+    /// stream, merging `fan_in` runs at a time into longer runs first if
+    /// there are too many to open at once.
+    fn merge(
+        &mut self,
+        mut paths: Vec<PathBuf>,
+        mut last: Vec<u32>,
+        fan_in: usize,
+    ) -> Result<Merge, ExternalError> {
+        let result = (|| {
+            while paths.len() + 1 > fan_in {
+                let batch: Vec<_> = paths.drain(..fan_in).collect();
+                let mut merge = Merge::new(open(&batch)?)?;
+                paths.push(self.write(|| merge.next())?);
+
+                for path in batch {
+                    fs::remove_file(path)?;
+                }
+            }
+
+            last.sort_unstable();
+            let mut runs = open(&paths)?;
+            runs.push(Run::Memory(last.into_iter()));
+            Merge::new(runs)
+        })();
+
+        result.map_err(|x| self.error(x))
+    }
+
+    /// Writes the IDs `next` produces, in order, to a new run file.
+    fn write(&mut self, mut next: impl FnMut() -> io::Result<Option<u32>>) -> io::Result<PathBuf> {
+        if !self.created {
+            fs::create_dir_all(&self.path)?;
+            self.created = true;
+        }
+
+        let path = self.path.join(format!("{}.run", self.files));
+        self.files += 1;
+
+        let mut writer = BufWriter::new(File::create(&path)?);
+        while let Some(id) = next()? {
+            writer.write_all(&id.to_le_bytes())?;
+        }
+        writer.flush()?;
+
+        Ok(path)
+    }
+
+    fn error(&self, error: io::Error) -> ExternalError {
+        ExternalError::Runs {
+            dir: self.path.clone(),
+            error,
+        }
+    }
+}
+
+impl Drop for RunDir {
+    fn drop(&mut self) {
+        if self.created {
+            let _ = fs::remove_dir_all(&self.path);
+        }
+    }
+}
+
+fn open(paths: &[PathBuf]) -> io::Result<Vec<Run>> {
+    paths
+        .iter()
+        .map(|x| Ok(Run::File(BufReader::new(File::open(x)?))))
+        .collect()
+}
+
+#[derive(Debug)]
+pub enum ExternalError {
+    Parse(ParseError),
+    Read(io::Error),
+    /// Writing or reading back the sorted runs failed.
+    Runs {
+        dir: PathBuf,
+        error: io::Error,
+    },
+}
+
+impl Display for ExternalError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ExternalError::Parse(error) => write!(f, "{}", error),
+            ExternalError::Read(error) => write!(f, "failed to read the input: {}", error),
+            ExternalError::Runs { dir, error } => {
+                write!(f, "failed to sort runs in {}: {}", dir.display(), error)
+            }
+        }
+    }
+}
+
+impl Error for ExternalError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ExternalError::Parse(error) => Some(error),
+            ExternalError::Read(error) => Some(error),
+            ExternalError::Runs { error, .. } => Some(error),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{days::day1::Day1, generate::Rng, Solution};
+
+    const EXAMPLE: &str = include_str!("../../inputs/day1.example.txt");
+
+    fn options(chunk_lines: usize, fan_in: usize, name: &str) -> Options {
+        Options {
+            chunk_lines,
+            fan_in,
+            temp_dir: env::temp_dir().join(format!("aoc-external-{}-{}", name, process::id())),
+        }
+    }
+
+    #[test]
+    fn example() {
+        let totals = solve(EXAMPLE.as_bytes(), &Options::default()).unwrap();
+
+        assert_eq!(totals.pairs, 6);
+        assert_eq!(totals.runs, 0);
+        assert_eq!(totals.distance, 11);
+        assert_eq!(totals.similarity, 31);
+    }
+
+    #[test]
+    fn agrees_with_in_memory() {
+        for seed in 0..5 {
+            let contents = Day1::generate(&mut Rng::new(seed), 200).unwrap();
+            let input = Day1::parse(&contents).unwrap();
+
+            // Chunks of one line and merging two runs at a time take the
+            // most rounds of merging
+            for (chunk_lines, fan_in) in [(1, 2), (7, 3), (200, 64)] {
+                let options = options(chunk_lines, fan_in, "agree");
+                let totals = solve(contents.as_bytes(), &options).unwrap();
+
+                assert_eq!(totals.runs, 200 / chunk_lines);
+                assert_eq!(totals.distance, Day1::part1(&input) as u128);
+                assert_eq!(totals.similarity, Day1::part2(&input) as u128);
+                assert!(fs::read_dir(&options.temp_dir).unwrap().next().is_none());
+            }
+        }
+
+        fs::remove_dir_all(options(1, 2, "agree").temp_dir).unwrap();
+    }
+
+    #[test]
+    fn parse_error_line() {
+        let error = solve("1 2\n\n3 x\n".as_bytes(), &options(1, 2, "error")).unwrap_err();
+
+        let ExternalError::Parse(error) = error else {
+            panic!("expected a parse error, got {:?}", error);
+        };
+        assert_eq!(error.line, 3);
+        assert_eq!(error.column, 3);
+        assert_eq!(error.found, "x");
+
+        let _ = fs::remove_dir_all(options(1, 2, "error").temp_dir);
+    }
+}
@@ -0,0 +1,723 @@
+//! Day 1 for lists that change, answering after every insertion or removal
+//! without sorting again.
+//!
+//! The similarity score is kept as a running sum from per-ID counts of both
+//! lists. The total distance between two sorted lists of the same length is
+//! the area between their counting functions, that is the sum over every
+//! possible ID `x` of `|d(x)|`, where `d(x)` is how many left IDs are at most
+//! `x` minus how many right IDs are. Inserting or removing an ID adds 1 or -1
+//! to `d` from that ID up, and `Area` works out how the sum changes.
+//!
+//! `Area` keeps the IDs where `d` steps up or down in order, split into
+//! blocks of around the square root of their number, each knowing how many
+//! IDs it covers at every value of `d`. An update rebuilds the block it lands
+//! in, and for every block after it counts the IDs where `d` moves away from
+//! zero and those where it moves towards it.
+//!
+//! Updating the similarity score takes constant time, and updating the
+//! distance O(sqrt(n) log n) amortized for n such IDs, however often the
+//! lists cross over. That is not logarithmic: keeping the sum of `|d|` while
+//! adding to every value from some point on has no known polylogarithmic
+//! structure, and ranking each list instead only moves the cost to the pairs
+//! whose ranks shift, which can be every pair.
+
+use std::{
+    error::Error,
+    fmt::{self, Display},
+    io::{self, BufRead, Write},
+};
+
+use ahash::AHashMap;
+
+use super::Day1;
+use crate::{parse, ParseError, Solution};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Side {
+    Left,
+    Right,
+}
+
+impl Side {
+    fn other(self) -> Self {
+        match self {
+            Side::Left => Side::Right,
+            Side::Right => Side::Left,
+        }
+    }
+}
+
+impl Display for Side {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Side::Left => write!(f, "left"),
+            Side::Right => write!(f, "right"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Update {
+    Insert(Side, u32),
+    Remove(Side, u32),
+}
+
+impl Update {
+    /// Parses one line of a command stream:
+    ///
+    /// - `<left> <right>` or `+ <left> <right>` inserts a pair
+    /// - `- <left> <right>` removes a pair
+    /// - `+l <id>`, `-l <id>`, `+r <id>` and `-r <id>` change one list
+    ///
+    /// Lines of a puzzle input therefore insert their pairs. `line` is one
+    /// line of `contents`, which errors are reported against.
+    pub fn parse_line(contents: &str, line: &str) -> Result<Vec<Self>, ParseError> {
+        let tokens: Vec<_> = line.split_whitespace().collect();
+        let (op, ids) = match tokens.first() {
+            Some(&x) if x.starts_with(['+', '-']) => (x, &tokens[1..]),
+            _ => ("+", &tokens[..]),
+        };
+
+        let sides: &[Side] = match &op[1..] {
+            "" => &[Side::Left, Side::Right],
+            "l" => &[Side::Left],
+            "r" => &[Side::Right],
+            _ => {
+                return Err(ParseError::new(
+                    Day1::DAY,
+                    contents,
+                    op,
+                    "`+`, `-`, `+l`, `-l`, `+r` or `-r`",
+                ))
+            }
+        };
+
+        if ids.len() < sides.len() {
+            return Err(ParseError::new(
+                Day1::DAY,
+                contents,
+                parse::end_of(line),
+                format!("{} location IDs", sides.len()),
+            ));
+        }
+        if let Some(extra) = ids.get(sides.len()) {
+            return Err(ParseError::new(
+                Day1::DAY,
+                contents,
+                extra,
+                "the end of the line",
+            ));
+        }
+
+        sides
+            .iter()
+            .zip(ids)
+            .map(|(&side, id)| {
+                let id = parse::number(Day1::DAY, contents, id, "a location ID")?;
+                Ok(match op.as_bytes()[0] {
+                    b'+' => Update::Insert(side, id),
+                    _ => Update::Remove(side, id),
+                })
+            })
+            .collect()
+    }
+}
+
+/// Both lists, kept up to date with both answers.
+#[derive(Debug, Clone, Default)]
+pub struct OnlineInput {
+    left: AHashMap<u32, u64>,
+    right: AHashMap<u32, u64>,
+    left_len: u64,
+    right_len: u64,
+    similarity: u64,
+    area: Area,
+}
+
+impl OnlineInput {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self, side: Side) -> u64 {
+        match side {
+            Side::Left => self.left_len,
+            Side::Right => self.right_len,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.left_len == 0 && self.right_len == 0
+    }
+
+    /// How many times `id` is in the list on `side`.
+    pub fn count(&self, side: Side, id: u32) -> u64 {
+        let counts = match side {
+            Side::Left => &self.left,
+            Side::Right => &self.right,
+        };
+        counts.get(&id).copied().unwrap_or_default()
+    }
+
+    /// Total distance between the sorted lists, or `None` while they have
+    /// different lengths and cannot be paired up.
+    pub fn total_distance(&self) -> Option<u64> {
+        (self.left_len == self.right_len).then_some(self.area.total)
+    }
+
+    pub fn similarity_score(&self) -> u64 {
+        self.similarity
+    }
+
+    pub fn insert(&mut self, side: Side, id: u32) {
+        let other = self.count(side.other(), id);
+        let (counts, len, delta) = match side {
+            Side::Left => (&mut self.left, &mut self.left_len, 1),
+            Side::Right => (&mut self.right, &mut self.right_len, -1),
+        };
+
+        *counts.entry(id).or_default() += 1;
+        *len += 1;
+        self.similarity += id as u64 * other;
+        self.area.add_from(id, delta);
+    }
+
+    /// Removes one `id` from the list on `side`, returning whether there was
+    /// one to remove.
+    pub fn remove(&mut self, side: Side, id: u32) -> bool {
+        let other = self.count(side.other(), id);
+        let (counts, len, delta) = match side {
+            Side::Left => (&mut self.left, &mut self.left_len, -1),
+            Side::Right => (&mut self.right, &mut self.right_len, 1),
+        };
+
+        let Some(count) = counts.get_mut(&id) else {
+            return false;
+        };
+        *count -= 1;
+        if *count == 0 {
+            counts.remove(&id);
+        }
+        *len -= 1;
+        self.similarity -= id as u64 * other;
+        self.area.add_from(id, delta);
+
+        true
+    }
+
+    /// Applies `updates` together, or none of them if one removes an ID that
+    /// is not there.
+    pub fn apply(&mut self, updates: &[Update]) -> Result<(), (Side, u32)> {
+        for update in updates {
+            if let Update::Remove(side, id) = *update {
+                let removed = updates
+                    .iter()
+                    .filter(|&&x| x == Update::Remove(side, id))
+                    .count() as u64;
+                if self.count(side, id) < removed {
+                    return Err((side, id));
+                }
+            }
+        }
+
+        for update in updates {
+            match *update {
+                Update::Insert(side, id) => self.insert(side, id),
+                Update::Remove(side, id) => {
+                    self.remove(side, id);
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Applies each line of `reader` as an update, writing both answers to `out`
+/// after every line as `<distance> <similarity>`, with `-` for the distance
+/// while the lists have different lengths. Blank lines are skipped.
+pub fn run(mut reader: impl BufRead, mut out: impl Write) -> Result<OnlineInput, OnlineError> {
+    let mut input = OnlineInput::new();
+
+    let mut line = String::new();
+    let mut number = 0;
+    loop {
+        line.clear();
+        if reader.read_line(&mut line).map_err(OnlineError::Io)? == 0 {
+            break;
+        }
+        number += 1;
+
+        let text = line.trim_end_matches(['\n', '\r']);
+        if text.trim().is_empty() {
+            continue;
+        }
+
+        let updates = Update::parse_line(text, text).map_err(|mut error| {
+            error.line = number;
+            OnlineError::Parse(error)
+        })?;
+        input
+            .apply(&updates)
+            .map_err(|(side, id)| OnlineError::Missing {
+                line: number,
+                side,
+                id,
+            })?;
+
+        match input.total_distance() {
+            Some(distance) => writeln!(out, "{} {}", distance, input.similarity_score()),
+            None => writeln!(out, "- {}", input.similarity_score()),
+        }
+        .map_err(OnlineError::Io)?;
+    }
+
+    Ok(input)
+}
+
+/// Fewest IDs in a block, so small inputs do not split into many tiny ones.
+const MIN_BLOCK: usize = 32;
+
+/// The sum of `|d(x)|` over every ID `x`, see the module docs.
+#[derive(Debug, Clone)]
+struct Area {
+    /// The IDs where `d` steps up or down, in order, in blocks of around
+    /// `block_size`. No block is empty.
+    blocks: Vec<Block>,
+    /// IDs in all the blocks.
+    len: usize,
+    /// How large the blocks were made the last time they were all rebuilt.
+    block_size: usize,
+    total: u64,
+}
+
+#[derive(Debug, Clone, Default)]
+struct Block {
+    /// Each ID with how much `d` steps by there, never 0.
+    steps: Vec<(u32, i64)>,
+    /// `d` just below the first ID.
+    base: i64,
+    /// The values `d` takes within the block, less `base`, in increasing
+    /// order.
+    levels: Vec<i64>,
+    /// How many IDs from the first one up to the next block's have `d` at
+    /// least as high as each of `levels`.
+    at_least: Vec<u64>,
+}
+
+impl Default for Area {
+    fn default() -> Self {
+        Self {
+            blocks: vec![],
+            len: 0,
+            block_size: MIN_BLOCK,
+            total: 0,
+        }
+    }
+}
+
+impl Block {
+    /// Works out `levels` and `at_least` again after `steps` changed, where
+    /// `next` is the first ID of the block after this one.
+    fn rebuild(&mut self, next: Option<u32>) {
+        let mut spans: Vec<_> = self.spans(0, next).collect();
+        spans.sort_unstable();
+
+        self.levels.clear();
+        self.at_least.clear();
+        let mut ids = 0;
+        for &(d, count) in spans.iter().rev() {
+            ids += count;
+            if self.levels.last() == Some(&d) {
+                *self.at_least.last_mut().unwrap() = ids;
+            } else {
+                self.levels.push(d);
+                self.at_least.push(ids);
+            }
+        }
+        self.levels.reverse();
+        self.at_least.reverse();
+    }
+
+    /// `d` from each ID until the next one where it steps, starting from
+    /// `base`, with how many IDs that covers. `d` is 0 past the last step
+    /// once the lists have the same length, so the last stretch is empty.
+    fn spans(&self, base: i64, next: Option<u32>) -> impl Iterator<Item = (i64, u64)> + '_ {
+        let ends = self.steps.iter().skip(1).map(|x| Some(x.0)).chain([next]);
+
+        self.steps
+            .iter()
+            .zip(ends)
+            .scan(base, |d, (&(id, step), end)| {
+                *d += step;
+                Some((*d, (end.unwrap_or(id) - id) as u64))
+            })
+    }
+
+    fn area(&self, next: Option<u32>) -> u64 {
+        self.spans(self.base, next)
+            .map(|(d, count)| d.unsigned_abs() * count)
+            .sum()
+    }
+
+    /// How many of the block's IDs have `d` at `min` or above.
+    fn count_at_least(&self, min: i64) -> u64 {
+        let i = self.levels.partition_point(|&x| x + self.base < min);
+        self.at_least.get(i).copied().unwrap_or(0)
+    }
+
+    /// Adds `delta`, 1 or -1, to `d` over the whole block and returns how
+    /// much its area changed: every ID where `d` moves away from 0 adds 1,
+    /// and every other one takes 1 away.
+    fn shift(&mut self, delta: i64) -> i64 {
+        let all = self.at_least.first().copied().unwrap_or(0) as i64;
+        let away = match delta {
+            1 => self.count_at_least(0) as i64,
+            _ => all - self.count_at_least(1) as i64,
+        };
+
+        self.base += delta;
+        2 * away - all
+    }
+}
+
+impl Area {
+    /// Adds `delta`, 1 or -1, to `d(x)` for every `x` from `start` up.
+    fn add_from(&mut self, start: u32, delta: i64) {
+        if self.blocks.is_empty() {
+            self.blocks.push(Block::default());
+        }
+
+        // The block holding `start`, or the one it goes at the end of
+        let mut b = self
+            .blocks
+            .partition_point(|x| x.steps.last().is_some_and(|&(id, _)| id < start))
+            .min(self.blocks.len() - 1);
+        let mut found = self.blocks[b].steps.binary_search_by_key(&start, |x| x.0);
+        if found == Err(0) && b > 0 {
+            b -= 1;
+            found = Err(self.blocks[b].steps.len());
+        }
+
+        // The block before has its last stretch end where this one starts
+        let first = match found {
+            Ok(0) => b.saturating_sub(1),
+            _ => b,
+        };
+        let before: u64 = (first..=b).map(|c| self.block_area(c)).sum();
+
+        let block = &mut self.blocks[b];
+        match found {
+            Ok(i) => {
+                block.steps[i].1 += delta;
+                if block.steps[i].1 == 0 {
+                    block.steps.remove(i);
+                    self.len -= 1;
+                }
+            }
+            Err(i) => {
+                block.steps.insert(i, (start, delta));
+                self.len += 1;
+            }
+        }
+
+        // The blocks from `rest` on only have `d` shifted
+        let mut rest = b + 1;
+        if self.blocks[b].steps.is_empty() {
+            self.blocks.remove(b);
+            rest = b;
+        }
+        let mut after = 0;
+        for c in first..rest {
+            let next = self.next_id(c);
+            self.blocks[c].rebuild(next);
+            after += self.block_area(c);
+        }
+
+        let shifted: i64 = self.blocks[rest..].iter_mut().map(|x| x.shift(delta)).sum();
+        self.total = (self.total + after - before)
+            .checked_add_signed(shifted)
+            .expect("the area between the lists overflowed");
+
+        if rest > b && self.blocks[b].steps.len() > 2 * self.block_size {
+            self.split(b);
+        }
+        self.rebalance();
+    }
+
+    fn next_id(&self, block: usize) -> Option<u32> {
+        self.blocks.get(block + 1).map(|x| x.steps[0].0)
+    }
+
+    fn block_area(&self, block: usize) -> u64 {
+        self.blocks[block].area(self.next_id(block))
+    }
+
+    /// Splits a block that grew too large in two, which leaves the area as
+    /// it is.
+    fn split(&mut self, block: usize) {
+        let first = &mut self.blocks[block];
+        let steps = first.steps.split_off(first.steps.len() / 2);
+        let base = first.base + first.steps.iter().map(|x| x.1).sum::<i64>();
+
+        self.blocks.insert(
+            block + 1,
+            Block {
+                steps,
+                base,
+                ..Block::default()
+            },
+        );
+        for c in [block, block + 1] {
+            let next = self.next_id(c);
+            self.blocks[c].rebuild(next);
+        }
+    }
+
+    /// Rebuilds every block at half the square root of the number of IDs,
+    /// which balances rebuilding one block against shifting the rest best,
+    /// once that has moved a long way from `block_size` or removals have
+    /// left too many small blocks.
+    fn rebalance(&mut self) {
+        let size = (self.len.isqrt() / 2).max(MIN_BLOCK);
+        let stale = size > 2 * self.block_size
+            || 2 * size < self.block_size
+            || self.blocks.len() > 2 * self.len / size + 4;
+        if !stale {
+            return;
+        }
+
+        let steps: Vec<_> = self.blocks.drain(..).flat_map(|x| x.steps).collect();
+        let mut base = 0;
+        for chunk in steps.chunks(size) {
+            self.blocks.push(Block {
+                steps: chunk.to_vec(),
+                base,
+                ..Block::default()
+            });
+            base += chunk.iter().map(|x| x.1).sum::<i64>();
+        }
+        for c in 0..self.blocks.len() {
+            let next = self.next_id(c);
+            self.blocks[c].rebuild(next);
+        }
+        self.block_size = size;
+    }
+}
+
+#[derive(Debug)]
+pub enum OnlineError {
+    Parse(ParseError),
+    /// A line removes an ID that is not in the list.
+    Missing {
+        line: usize,
+        side: Side,
+        id: u32,
+    },
+    Io(io::Error),
+}
+
+impl Display for OnlineError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OnlineError::Parse(error) => write!(f, "{}", error),
+            OnlineError::Missing { line, side, id } => {
+                write!(
+                    f,
+                    "line {}: the {} list has no {} to remove",
+                    line, side, id
+                )
+            }
+            OnlineError::Io(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+impl Error for OnlineError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            OnlineError::Parse(error) => Some(error),
+            OnlineError::Missing { .. } => None,
+            OnlineError::Io(error) => Some(error),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{days::day1::SortedInput, generate::Rng};
+
+    const EXAMPLE: &str = include_str!("../../inputs/day1.example.txt");
+
+    #[test]
+    fn example() {
+        let mut out = vec![];
+        let input = run(EXAMPLE.as_bytes(), &mut out).unwrap();
+
+        assert_eq!(input.total_distance(), Some(11));
+        assert_eq!(input.similarity_score(), 31);
+        assert_eq!(
+            String::from_utf8(out).unwrap().lines().last(),
+            Some("11 31")
+        );
+    }
+
+    #[test]
+    fn commands() {
+        let mut out = vec![];
+        let commands = "+ 3 4\n+l 4\n+r 3\n-l 3\n-r 4\n- 4 3\n";
+        let input = run(commands.as_bytes(), &mut out).unwrap();
+
+        assert!(input.is_empty());
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "1 0\n- 4\n0 7\n- 4\n1 0\n0 0\n"
+        );
+    }
+
+    #[test]
+    fn agrees_with_sorting() {
+        let mut rng = Rng::new(7);
+        let mut input = OnlineInput::new();
+        let mut left = vec![];
+        let mut right = vec![];
+
+        for _ in 0..2000 {
+            if !left.is_empty() && rng.one_in(3) {
+                let x = left.swap_remove(rng.below(left.len()));
+                let y = right.swap_remove(rng.below(right.len()));
+                input
+                    .apply(&[
+                        Update::Remove(Side::Left, x),
+                        Update::Remove(Side::Right, y),
+                    ])
+                    .unwrap();
+            } else {
+                // Few distinct IDs, so the lists keep crossing over
+                let x = rng.range(0..=50) as u32 * 1000;
+                let y = rng.range(0..=50) as u32 * 1000;
+                left.push(x);
+                right.push(y);
+                input
+                    .apply(&[
+                        Update::Insert(Side::Left, x),
+                        Update::Insert(Side::Right, y),
+                    ])
+                    .unwrap();
+            }
+
+            let sorted = SortedInput::new(left.clone(), right.clone());
+            assert_eq!(input.total_distance(), Some(Day1::part1(&sorted) as u64));
+            assert_eq!(input.similarity_score(), sorted.similarity_score() as u64);
+        }
+
+        // Only IDs where `d` steps are kept
+        assert!(input.area.len <= 51);
+        check_blocks(&input.area);
+    }
+
+    #[test]
+    fn agrees_with_sorting_in_many_blocks() {
+        let mut rng = Rng::new(11);
+        let mut input = OnlineInput::new();
+        let mut left = vec![];
+        let mut right = vec![];
+
+        // Grows to thousands of pairs, which splits blocks and rebuilds them
+        // at larger sizes, then shrinks back down so they are rebuilt smaller
+        let mut largest = 0;
+        let mut checked = 0;
+        for round in 0..30_000 {
+            let removals = if round < 15_000 { 1 } else { 9 };
+            if !left.is_empty() && rng.below(10) < removals {
+                let x = left.swap_remove(rng.below(left.len()));
+                let y = right.swap_remove(rng.below(right.len()));
+                assert!(input.remove(Side::Left, x));
+                assert!(input.remove(Side::Right, y));
+            } else {
+                let x = rng.range(0..=1_000_000) as u32;
+                let y = rng.range(0..=1_000_000) as u32;
+                left.push(x);
+                right.push(y);
+                input.insert(Side::Left, x);
+                input.insert(Side::Right, y);
+            }
+
+            if round % 499 == 0 {
+                let sorted = SortedInput::new(left.clone(), right.clone());
+                assert_eq!(input.total_distance(), Some(Day1::part1(&sorted) as u64));
+                check_blocks(&input.area);
+                checked += 1;
+            }
+            largest = largest.max(input.area.block_size);
+        }
+
+        assert!(checked > 20);
+        assert!(largest > 2 * MIN_BLOCK);
+        assert_eq!(input.area.block_size, MIN_BLOCK);
+    }
+
+    /// Checks the blocks hold every step in order and agree with each other.
+    fn check_blocks(area: &Area) {
+        let steps: Vec<_> = area.blocks.iter().flat_map(|x| &x.steps).collect();
+        assert_eq!(steps.len(), area.len);
+        assert!(steps.windows(2).all(|x| x[0].0 < x[1].0));
+        assert!(steps.iter().all(|x| x.1 != 0));
+
+        let mut base = 0;
+        for (i, block) in area.blocks.iter().enumerate() {
+            assert!(!block.steps.is_empty());
+            assert!(block.steps.len() <= 2 * area.block_size);
+            assert_eq!(block.base, base);
+            base += block.steps.iter().map(|x| x.1).sum::<i64>();
+
+            let mut rebuilt = block.clone();
+            rebuilt.rebuild(area.next_id(i));
+            assert_eq!(rebuilt.levels, block.levels);
+            assert_eq!(rebuilt.at_least, block.at_least);
+        }
+        assert!(area.blocks.len() <= 2 * area.len / MIN_BLOCK + 4);
+    }
+
+    #[test]
+    fn missing() {
+        let error = run("1 2\n- 1 3\n".as_bytes(), io::sink()).unwrap_err();
+        assert!(matches!(
+            error,
+            OnlineError::Missing {
+                line: 2,
+                side: Side::Right,
+                id: 3
+            }
+        ));
+
+        let mut input = OnlineInput::new();
+        input.insert(Side::Left, 5);
+        let removes = [Update::Remove(Side::Left, 5), Update::Remove(Side::Left, 5)];
+        assert_eq!(input.apply(&removes), Err((Side::Left, 5)));
+        assert_eq!(input.count(Side::Left, 5), 1);
+    }
+
+    #[test]
+    fn parse_errors() {
+        let error = run("1 2\n+x 3\n".as_bytes(), io::sink()).unwrap_err();
+        let OnlineError::Parse(error) = error else {
+            panic!("expected a parse error, got {:?}", error);
+        };
+        assert_eq!((error.line, error.column), (2, 1));
+        assert_eq!(error.found, "+x");
+        assert_eq!(error.excerpt().unwrap(), "  |\n2 | +x 3\n  | ^^");
+
+        let error = run("1 2\n\n-l 1 x\n".as_bytes(), io::sink()).unwrap_err();
+        let OnlineError::Parse(error) = error else {
+            panic!("expected a parse error, got {:?}", error);
+        };
+        assert_eq!((error.line, error.column), (3, 6));
+        assert_eq!(error.excerpt().unwrap(), "  |\n3 | -l 1 x\n  |      ^");
+
+        for (line, found) in [("-l 1 2", "2"), ("+ 1", ""), ("1 a", "a")] {
+            assert_eq!(Update::parse_line(line, line).unwrap_err().found, found);
+        }
+    }
+}
@@ -0,0 +1,188 @@
+use itertools::Itertools;
+use petgraph::{graph::NodeIndex, visit::Bfs, Graph};
+
+use crate::{cancel::Token, generate::Rng, geometry::Direction, grid::Grid, ParseError, Solution};
+
+pub struct Day10;
+
+struct Node {
+    height: isize,
+}
+
+pub struct DiGraph {
+    graph: Graph<Node, ()>,
+    zeroes: Vec<NodeIndex>,
+}
+
+impl DiGraph {
+    fn parse(contents: &str) -> Result<Self, ParseError> {
+        let heights = Grid::parse(Day10::DAY, contents, Node::new)?;
+
+        let mut graph = Graph::new();
+        let mut zeroes = vec![];
+
+        let nodes = heights.map(|x| {
+            let current = graph.add_node(Node { height: x.height });
+
+            // Keep track of entry points
+            if x.height == 0 {
+                zeroes.push(current);
+            }
+
+            current
+        });
+
+        // Only link back to the left and up, so every pair is visited once
+        for (pos, &current) in nodes.iter() {
+            for direction in [Direction::Left, Direction::Up] {
+                if let Some(other) = nodes.step(pos, direction.delta()) {
+                    Self::add_node_if_adjacent(&mut graph, current, nodes[other]);
+                }
+            }
+        }
+
+        Ok(Self { graph, zeroes })
+    }
+
+    fn add_node_if_adjacent(graph: &mut Graph<Node, ()>, current: NodeIndex, other: NodeIndex) {
+        let (min, max) = if graph[current].height > graph[other].height {
+            (other, current)
+        } else {
+            (current, other)
+        };
+
+        if (graph[max].height - graph[min].height) == 1 {
+            _ = graph.add_edge(min, max, ());
+        }
+    }
+
+    fn count_paths(&self) -> usize {
+        let mut count = 0;
+        for zero in &self.zeroes {
+            let mut bfs = Bfs::new(&self.graph, *zero);
+
+            while let Some(nx) = bfs.next(&self.graph) {
+                if self.graph[nx].height == 9 {
+                    count += 1;
+                }
+            }
+        }
+        count
+    }
+
+    fn count_path_scores(&self) -> usize {
+        let mut count = 0;
+        let cancel = Token::current();
+        for zero in &self.zeroes {
+            let mut stack = vec![(*zero, vec![*zero])];
+
+            while let Some((current, path)) = stack.pop() {
+                if cancel.is_cancelled() {
+                    return count;
+                }
+
+                if self.graph[current].height == 9 {
+                    count += 1
+                } else {
+                    for neighbor in self.graph.neighbors(current) {
+                        if path.contains(&neighbor) {
+                            continue;
+                        }
+
+                        let mut new_path = path.clone();
+                        new_path.push(neighbor);
+                        stack.push((neighbor, new_path));
+                    }
+                }
+            }
+        }
+        count
+    }
+}
+
+impl Node {
+    fn new(height: char) -> Self {
+        let height = if !height.is_numeric() {
+            // -2 so that it's not adjacent to 0
+            -2
+        } else {
+            height as isize - '0' as isize
+        };
+
+        Self { height }
+    }
+}
+
+impl Solution for Day10 {
+    const DAY: u8 = 10;
+
+    type Input = DiGraph;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(contents: &str) -> Result<Self::Input, ParseError> {
+        DiGraph::parse(contents)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Part1 {
+        input.count_paths()
+    }
+
+    fn part2(input: &Self::Input) -> Self::Part2 {
+        input.count_path_scores()
+    }
+
+    /// A `size` by `size` map of random heights, with `size` trails from 0
+    /// to 9 carved into it by random walks.
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        let size = size.max(1);
+        let mut heights: Vec<Vec<_>> = (0..size)
+            .map(|_| (0..size).map(|_| rng.range(0..=9)).collect())
+            .collect();
+
+        for _ in 0..size {
+            let mut position = (rng.below(size), rng.below(size));
+            let mut trail = vec![];
+
+            for height in 0..=9 {
+                heights[position.0][position.1] = height;
+                trail.push(position);
+
+                let (row, column) = position;
+                let neighbors: Vec<_> = [(row + 1, column), (row, column + 1)]
+                    .into_iter()
+                    .chain(row.checked_sub(1).map(|x| (x, column)))
+                    .chain(column.checked_sub(1).map(|x| (row, x)))
+                    .filter(|&(i, j)| i < size && j < size && !trail.contains(&(i, j)))
+                    .collect();
+                if neighbors.is_empty() {
+                    break;
+                }
+                position = *rng.pick(&neighbors);
+            }
+        }
+
+        let contents: String = heights.iter().map(|x| x.iter().join("") + "\n").collect();
+
+        Some(contents)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../inputs/day10.example.txt");
+
+    #[test]
+    fn part1_example() {
+        let input = Day10::parse(EXAMPLE).unwrap();
+        assert_eq!(Day10::part1(&input), 36);
+    }
+
+    #[test]
+    fn part2_example() {
+        let input = Day10::parse(EXAMPLE).unwrap();
+        assert_eq!(Day10::part2(&input), 81);
+    }
+}
@@ -0,0 +1,179 @@
+use ahash::AHashMap;
+use itertools::Itertools;
+
+use crate::{cancel::Token, generate::Rng, parse, ParseError, Solution};
+
+pub struct Day11;
+
+#[derive(Clone)]
+pub struct Stones {
+    stones: AHashMap<String, usize>,
+}
+
+impl Stones {
+    fn parse(contents: &str) -> Result<Self, ParseError> {
+        let mut stones = AHashMap::new();
+
+        for x in contents.split_whitespace() {
+            parse::number::<usize>(Day11::DAY, contents, x, "a number engraved on a stone")?;
+
+            stones
+                .entry(x.to_string())
+                .and_modify(|v| *v += 1)
+                .or_insert(1);
+        }
+
+        Ok(Self { stones })
+    }
+
+    fn count_at_iteration(&mut self, iteration: usize) -> usize {
+        let mut cache = AHashMap::new();
+        let cancel = Token::current();
+        for _ in 0..iteration {
+            if cancel.is_cancelled() {
+                break;
+            }
+
+            let mut it_keys = AHashMap::new();
+
+            for i in self.stones.keys() {
+                match cache.get(i) {
+                    Some(value) => {
+                        Self::update_count(&mut it_keys, value, self.stones[i]);
+                    }
+                    None => {
+                        let value = Self::get_next_value(i);
+                        Self::update_count(&mut it_keys, &value, self.stones[i]);
+                        cache.insert(i.to_string(), value);
+                    }
+                }
+            }
+            self.stones = it_keys;
+        }
+
+        self.stones.values().sum()
+    }
+
+    fn update_count(map: &mut AHashMap<String, usize>, keys: &(String, Option<String>), i: usize) {
+        map.entry(keys.0.to_string())
+            .and_modify(|x| *x += i)
+            .or_insert(i);
+
+        if let Some(k) = &keys.1 {
+            map.entry(k.to_string())
+                .and_modify(|x| *x += i)
+                .or_insert(i);
+        };
+    }
+
+    fn get_next_value(key: &String) -> (String, Option<String>) {
+        if key == "0" {
+            (String::from("1"), None)
+        } else if key.len().is_multiple_of(2) {
+            let (v0, v1) = key.split_at(key.len() / 2);
+
+            let v0 = v0.parse::<usize>();
+            assert!(v0.is_ok());
+            let v1 = v1.parse::<usize>();
+            assert!(v1.is_ok());
+
+            (v0.unwrap().to_string(), Some(v1.unwrap().to_string()))
+        } else {
+            let key = key.parse::<usize>();
+            assert!(key.is_ok());
+
+            (format!("{}", key.unwrap() * 2024), None)
+        }
+    }
+}
+
+impl Solution for Day11 {
+    const DAY: u8 = 11;
+
+    type Input = Stones;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(contents: &str) -> Result<Self::Input, ParseError> {
+        Stones::parse(contents)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Part1 {
+        input.clone().count_at_iteration(25)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Part2 {
+        input.clone().count_at_iteration(75)
+    }
+
+    /// `size` stones, a quarter of them engraved with 0 and the rest with
+    /// numbers of up to 7 digits. No stones give an empty line.
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        let stones: Vec<_> = (0..size)
+            .map(|_| match rng.below(4) {
+                0 => 0,
+                1 => rng.range(1..=9),
+                _ => rng.range(10..=9_999_999),
+            })
+            .collect();
+
+        Some(stones.iter().join(" ") + "\n")
+    }
+}
+
+/// Keeps every stone in one long list while blinking, see `differential`.
+pub(crate) fn brute_force_part1(contents: &str) -> String {
+    let mut stones: Vec<u64> = contents
+        .split_whitespace()
+        .map(|x| x.parse().unwrap())
+        .collect();
+
+    for _ in 0..25 {
+        stones = stones
+            .iter()
+            .flat_map(|&x| {
+                let digits = x.to_string();
+                if x == 0 {
+                    vec![1]
+                } else if digits.len() % 2 == 0 {
+                    let (left, right) = digits.split_at(digits.len() / 2);
+                    vec![left.parse().unwrap(), right.parse().unwrap()]
+                } else {
+                    vec![x * 2024]
+                }
+            })
+            .collect();
+    }
+
+    stones.len().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../inputs/day11.example.txt");
+
+    #[test]
+    fn part1_example() {
+        let input = Day11::parse(EXAMPLE).unwrap();
+        assert_eq!(Day11::part1(&input), 55312);
+    }
+
+    #[test]
+    fn no_stones() {
+        let contents = Day11::generate(&mut Rng::new(0), 0).unwrap();
+        assert_eq!(contents, "\n");
+
+        let input = Day11::parse(&contents).unwrap();
+        assert_eq!(Day11::part1(&input), 0);
+        assert_eq!(Day11::part2(&input), 0);
+        assert_eq!(brute_force_part1(&contents), "0");
+    }
+
+    #[test]
+    fn six_blinks() {
+        let mut stones = Day11::parse(EXAMPLE).unwrap();
+        assert_eq!(stones.count_at_iteration(6), 22);
+    }
+}
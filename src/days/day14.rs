@@ -0,0 +1,158 @@
+use itertools::Itertools;
+
+use crate::{generate::Rng, geometry::Point, parse, ParseError, Solution, Unsolved};
+
+pub struct Day14;
+
+#[derive(Debug, Clone)]
+pub struct Board {
+    bounds: Point,
+    robots: Vec<Robot>,
+}
+
+#[derive(Debug, Clone)]
+struct Robot {
+    position: Point,
+    velocity: Point,
+}
+
+impl Board {
+    fn parse(contents: &str, bounds: Point) -> Result<Self, ParseError> {
+        Ok(Self {
+            bounds,
+            robots: contents
+                .trim()
+                .lines()
+                .map(|x| Robot::parse(contents, x))
+                .collect::<Result<_, _>>()?,
+        })
+    }
+
+    fn safety_factor(&self) -> usize {
+        let middle = Point::new(self.bounds.row / 2, self.bounds.column / 2);
+
+        let mut counts = (0, 0, 0, 0);
+        self.robots
+            .iter()
+            .filter(|x| x.position.row != middle.row && x.position.column != middle.column)
+            .for_each(|x| {
+                match (
+                    x.position.row > middle.row,
+                    x.position.column > middle.column,
+                ) {
+                    (true, true) => counts.3 += 1,
+                    (true, false) => counts.2 += 1,
+                    (false, true) => counts.1 += 1,
+                    (false, false) => counts.0 += 1,
+                }
+            });
+
+        counts.0 * counts.1 * counts.2 * counts.3
+    }
+
+    fn move_n_times(&mut self, n: usize) {
+        for x in self.robots.iter_mut() {
+            x.move_n_times(n, &self.bounds);
+        }
+    }
+}
+
+impl Robot {
+    fn parse(contents: &str, line: &str) -> Result<Self, ParseError> {
+        let tuple = line.split_whitespace().collect_tuple().ok_or_else(|| {
+            ParseError::new(
+                Day14::DAY,
+                contents,
+                line.trim(),
+                "a robot like `p=0,4 v=3,-3`",
+            )
+        })?;
+
+        Self::parse_tuple(contents, tuple)
+    }
+
+    fn parse_tuple(contents: &str, tuple: (&str, &str)) -> Result<Self, ParseError> {
+        Ok(Self {
+            position: parse_point(contents, tuple.0, "p=", "a position")?,
+            velocity: parse_point(contents, tuple.1, "v=", "a velocity")?,
+        })
+    }
+
+    fn move_n_times(&mut self, count: usize, bounds: &Point) {
+        self.position = (self.position + self.velocity * count as isize).wrap(bounds);
+    }
+}
+
+/// Parses `p=<x>,<y>` style strings, where `x` is the column and `y` the row.
+fn parse_point(
+    contents: &str,
+    string: &str,
+    prefix: &str,
+    expected: &str,
+) -> Result<Point, ParseError> {
+    let (x, y) = string
+        .strip_prefix(prefix)
+        .and_then(|x| x.split_once(','))
+        .ok_or_else(|| {
+            ParseError::new(Day14::DAY, contents, string, format!("`{}<x>,<y>`", prefix))
+        })?;
+
+    Ok(Point::new(
+        parse::number(Day14::DAY, contents, y, expected)?,
+        parse::number(Day14::DAY, contents, x, expected)?,
+    ))
+}
+
+impl Solution for Day14 {
+    const DAY: u8 = 14;
+
+    type Input = Board;
+    type Part1 = usize;
+    type Part2 = Unsolved;
+
+    fn parse(contents: &str) -> Result<Self::Input, ParseError> {
+        Board::parse(contents, Point::new(103, 101))
+    }
+
+    fn part1(input: &Self::Input) -> Self::Part1 {
+        let mut board = input.clone();
+        board.move_n_times(100);
+        board.safety_factor()
+    }
+
+    fn part2(_: &Self::Input) -> Self::Part2 {
+        Unsolved
+    }
+
+    /// `size` robots anywhere on the 101 by 103 board.
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        let mut contents = String::new();
+
+        for _ in 0..size {
+            contents.push_str(&format!(
+                "p={},{} v={},{}\n",
+                rng.range(0..=100),
+                rng.range(0..=102),
+                rng.range(-100..=100),
+                rng.range(-100..=100)
+            ));
+        }
+
+        Some(contents)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../inputs/day14.example.txt");
+
+    #[test]
+    fn part1_example() {
+        // The example uses a smaller board than the real input
+        let mut board = Board::parse(EXAMPLE, Point::new(7, 11)).unwrap();
+        board.move_n_times(100);
+        assert_eq!(board.safety_factor(), 12);
+    }
+}
@@ -0,0 +1,200 @@
+use crate::{
+    generate::Rng,
+    geometry::{Direction, Point},
+    grid::Grid,
+    parse, ParseError, Solution, Unsolved,
+};
+
+pub struct Day15;
+
+#[derive(Debug, Clone)]
+pub struct State {
+    robot: Point,
+    grid: Grid<char>,
+    instructions: Vec<Direction>,
+}
+
+impl State {
+    fn parse(contents: &str) -> Result<Self, ParseError> {
+        let trimmed = contents.trim();
+        let (map, moves) = trimmed.split_once("\n\n").unwrap_or((trimmed, ""));
+
+        let grid = Grid::parse(Day15::DAY, map, |x| x)?;
+
+        let robots: Vec<_> = grid.find_all(&'@').collect();
+        let robot = match robots[..] {
+            [robot] => robot,
+            [] => {
+                return Err(ParseError::new(
+                    Day15::DAY,
+                    contents,
+                    parse::end_of(map),
+                    "a robot (`@`)",
+                ))
+            }
+            [_, second, ..] => {
+                let row = map.lines().nth(second.row as usize).unwrap_or(map).trim();
+                return Err(ParseError::new(Day15::DAY, contents, row, "a single robot"));
+            }
+        };
+
+        let mut instructions = vec![];
+        for x in moves.lines() {
+            for (j, c) in x.char_indices() {
+                instructions.push(parse_direction(contents, &x[j..j + c.len_utf8()])?);
+            }
+        }
+
+        Ok(Self {
+            robot,
+            grid,
+            instructions,
+        })
+    }
+
+    fn run_instructions(&mut self) {
+        for i in 0..self.instructions.len() {
+            self.do_move_at(i);
+        }
+    }
+
+    fn do_move_at(&mut self, index: usize) {
+        let delta = self.instructions[index].delta();
+
+        // Boxes can only be pushed if there is a free tile behind them
+        let mut dot = None;
+
+        for (pos, &x) in self.grid.line(self.robot, delta).skip(1) {
+            if x == '#' {
+                break;
+            } else if x == '.' {
+                dot = Some(pos);
+                break;
+            }
+        }
+
+        let dot = match dot {
+            Some(dot) => dot,
+            None => return,
+        };
+
+        let next = self.robot + delta;
+
+        // Shifting a row of boxes by one is the same as moving the first box
+        // to the free tile
+        if next != dot {
+            self.grid[dot] = 'O';
+        }
+
+        self.grid[self.robot] = '.';
+
+        self.robot = next;
+        self.grid[self.robot] = '@';
+    }
+
+    fn gps_sum(&self) -> usize {
+        self.grid
+            .find_all(&'O')
+            .map(|x| (100 * x.row + x.column) as usize)
+            .sum()
+    }
+}
+
+fn parse_direction(contents: &str, c: &str) -> Result<Direction, ParseError> {
+    match c {
+        "^" => Ok(Direction::Up),
+        "v" => Ok(Direction::Down),
+        "<" => Ok(Direction::Left),
+        ">" => Ok(Direction::Right),
+        _ => Err(ParseError::new(
+            Day15::DAY,
+            contents,
+            c,
+            "a move (`^`, `v`, `<` or `>`)",
+        )),
+    }
+}
+
+impl Solution for Day15 {
+    const DAY: u8 = 15;
+
+    type Input = State;
+    type Part1 = usize;
+    type Part2 = Unsolved;
+
+    fn parse(contents: &str) -> Result<Self::Input, ParseError> {
+        State::parse(contents)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Part1 {
+        let mut state = input.clone();
+        state.run_instructions();
+        state.gps_sum()
+    }
+
+    fn part2(_: &Self::Input) -> Self::Part2 {
+        Unsolved
+    }
+
+    /// A `size` by `size` warehouse inside a wall, with boxes, inner walls
+    /// and the robot, followed by `10 * size` moves.
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        let size = size.max(3);
+        let robot = (
+            rng.range(1..=size as i64 - 2),
+            rng.range(1..=size as i64 - 2),
+        );
+
+        let mut contents = String::new();
+        for i in 0..size as i64 {
+            for j in 0..size as i64 {
+                contents.push(if (i, j) == robot {
+                    '@'
+                } else if i == 0
+                    || j == 0
+                    || i == size as i64 - 1
+                    || j == size as i64 - 1
+                    || rng.one_in(10)
+                {
+                    '#'
+                } else if rng.one_in(3) {
+                    'O'
+                } else {
+                    '.'
+                });
+            }
+            contents.push('\n');
+        }
+
+        let moves: Vec<_> = (0..10 * size)
+            .map(|_| *rng.pick(&['^', 'v', '<', '>']))
+            .collect();
+        for line in moves.chunks(70) {
+            contents.push('\n');
+            contents.extend(line);
+        }
+        contents.push('\n');
+
+        Some(contents)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../inputs/day15.example.txt");
+    const SMALL_EXAMPLE: &str = include_str!("../inputs/day15.example2.txt");
+
+    #[test]
+    fn part1_large_example() {
+        let input = Day15::parse(EXAMPLE).unwrap();
+        assert_eq!(Day15::part1(&input), 10092);
+    }
+
+    #[test]
+    fn part1_small_example() {
+        let input = Day15::parse(SMALL_EXAMPLE).unwrap();
+        assert_eq!(Day15::part1(&input), 2028);
+    }
+}
@@ -0,0 +1,445 @@
+use std::fmt::{self, Display};
+
+use ahash::AHashMap;
+use itertools::Itertools;
+
+use crate::{generate::Rng, parse, ParseError, Solution};
+
+pub struct Day2;
+
+#[derive(Debug)]
+pub struct Row(Vec<u32>);
+
+impl Row {
+    pub fn parse_lines(contents: &str) -> Result<Vec<Self>, ParseError> {
+        contents
+            .trim()
+            .lines()
+            .map(|x| Row::parse_row(contents, x))
+            .collect()
+    }
+
+    fn parse_row(contents: &str, row: &str) -> Result<Self, ParseError> {
+        let row = row.trim();
+        let items: Vec<_> = row
+            .split(' ')
+            .map(|x| parse::number(Day2::DAY, contents, x, "a level"))
+            .collect::<Result<_, _>>()?;
+
+        if items.len() < 2 {
+            return Err(ParseError::new(
+                Day2::DAY,
+                contents,
+                row,
+                "a report of at least two levels",
+            ));
+        }
+
+        Ok(Self(items))
+    }
+
+    pub fn is_safe(&self) -> bool {
+        self.violation().is_none()
+    }
+
+    /// The first pair of neighbouring levels that makes the report unsafe.
+    pub fn violation(&self) -> Option<Violation> {
+        if self.0.len() < 2 {
+            return None;
+        }
+        let direction = Direction::parse(self.0[0], self.0[1]);
+
+        for (index, x) in self.0.windows(2).enumerate() {
+            let diff = x[0] as i32 - x[1] as i32;
+
+            let reason = if diff == 0 {
+                Reason::Flat
+            } else if !direction.matches(&Direction::new(diff)) {
+                Reason::Turn
+            } else if diff.abs() > 3 {
+                Reason::Jump
+            } else {
+                continue;
+            };
+
+            return Some(Violation { index, reason });
+        }
+
+        None
+    }
+
+    /// A level whose removal makes an unsafe report safe, if there is one.
+    /// Removing a level away from the first violation leaves it in place,
+    /// so only the levels around it need trying.
+    pub fn level_to_remove(&self) -> Option<usize> {
+        let Violation { index, .. } = self.violation()?;
+
+        (index.saturating_sub(1)..=index + 1).find(|&i| {
+            let mut levels = self.0.clone();
+            levels.remove(i);
+            Row(levels).is_safe()
+        })
+    }
+
+    /// Whether the report is safe with one level removed, as the Problem
+    /// Dampener allows.
+    pub fn is_safe_dampened(&self) -> bool {
+        self.is_safe_removing(1)
+    }
+
+    /// Whether the report is safe with at most `tolerance` levels removed.
+    pub fn is_safe_removing(&self, tolerance: usize) -> bool {
+        self.min_removals() <= tolerance
+    }
+
+    /// Fewest levels to remove for the report to be safe, in whichever
+    /// direction needs fewer. Reports have at least two levels, so this is
+    /// at most one less than their length.
+    pub fn min_removals(&self) -> usize {
+        if self.is_safe() {
+            return 0;
+        }
+
+        let mut best = AHashMap::with_capacity(self.0.len());
+        let kept = [Direction::Ascending, Direction::Descending]
+            .iter()
+            .map(|x| self.longest_safe(x, &mut best))
+            .max()
+            .unwrap_or_default();
+
+        self.0.len() - kept
+    }
+
+    /// Length of the longest safe report left after removing levels, going in
+    /// `direction`. The longest one ending in a level extends the longest
+    /// ending in a level 1 to 3 before it, so keeping the best length for
+    /// each level seen so far in `best` takes linear time.
+    fn longest_safe(&self, direction: &Direction, best: &mut AHashMap<u32, usize>) -> usize {
+        best.clear();
+        let mut longest = 0;
+
+        for &level in &self.0 {
+            let length = 1
+                + (1..=3)
+                    .filter_map(|step| {
+                        let before = match direction {
+                            Direction::Ascending => level.checked_sub(step)?,
+                            Direction::Descending => level.checked_add(step)?,
+                            Direction::Neutral => return None,
+                        };
+                        best.get(&before).copied()
+                    })
+                    .max()
+                    .unwrap_or_default();
+
+            let entry = best.entry(level).or_default();
+            *entry = (*entry).max(length);
+            longest = longest.max(length);
+        }
+
+        longest
+    }
+}
+
+/// Neighbouring levels that break a rule, `index` and `index + 1`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Violation {
+    pub index: usize,
+    pub reason: Reason,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Reason {
+    /// The levels go the other way from the first two.
+    Turn,
+    /// The levels are the same.
+    Flat,
+    /// The levels differ by more than 3.
+    Jump,
+}
+
+/// Renders a report with whether it is safe and, if not, markers under the
+/// first levels that break a rule and the level to remove if one will do,
+/// e.g.
+///
+/// ```text
+/// 1 3 2 4 5  safe with a level removed
+///   ^ ^ 3 to 2 goes down after going up
+///   x remove this level
+/// ```
+pub struct Diagnosis<'a>(pub &'a Row);
+
+impl Display for Diagnosis<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let levels = &self.0 .0;
+        let line = levels.iter().join(" ");
+
+        let Some(Violation { index, reason }) = self.0.violation() else {
+            return writeln!(f, "{}  safe", line);
+        };
+
+        let fix = self.0.level_to_remove();
+        match fix {
+            Some(_) => writeln!(f, "{}  safe with a level removed", line)?,
+            None => writeln!(
+                f,
+                "{}  unsafe, needs {} levels removed",
+                line,
+                self.0.min_removals()
+            )?,
+        }
+
+        // Where each level starts and ends on the line
+        let spans: Vec<_> = levels
+            .iter()
+            .scan(0, |start, x| {
+                let span = (*start, *start + x.to_string().len());
+                *start = span.1 + 1;
+                Some(span)
+            })
+            .collect();
+        let markers = |marked: &[usize], marker: char| {
+            let mut markers = String::new();
+            for &i in marked {
+                let (start, end) = spans[i];
+                markers.push_str(&" ".repeat(start - markers.len()));
+                markers.push_str(&marker.to_string().repeat(end - start));
+            }
+            markers
+        };
+
+        let (from, to) = (levels[index], levels[index + 1]);
+        let explanation = match reason {
+            Reason::Turn => {
+                let (now, before) = if from > to {
+                    ("down", "up")
+                } else {
+                    ("up", "down")
+                };
+                format!("{} to {} goes {} after going {}", from, to, now, before)
+            }
+            Reason::Flat => format!("{} to {} does not change", from, to),
+            Reason::Jump => format!(
+                "{} to {} changes by {}, more than 3",
+                from,
+                to,
+                from.abs_diff(to)
+            ),
+        };
+        writeln!(f, "{} {}", markers(&[index, index + 1], '^'), explanation)?;
+
+        if let Some(i) = fix {
+            writeln!(f, "{} remove this level", markers(&[i], 'x'))?;
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+enum Direction {
+    Ascending,
+    Descending,
+    Neutral,
+}
+
+impl Direction {
+    pub fn new(value: i32) -> Self {
+        match value {
+            b if b > 0 => Self::Descending,
+            b if b < 0 => Self::Ascending,
+            _ => Self::Neutral,
+        }
+    }
+
+    pub fn parse(lhs: u32, rhs: u32) -> Self {
+        let diff = lhs as i32 - rhs as i32;
+        Self::new(diff)
+    }
+
+    pub fn matches(&self, other: &Self) -> bool {
+        matches!(
+            (self, other),
+            (Self::Ascending, Self::Ascending)
+                | (Self::Descending, Self::Descending)
+                | (Self::Neutral, Self::Neutral)
+        )
+    }
+}
+
+impl Solution for Day2 {
+    const DAY: u8 = 2;
+
+    type Input = Vec<Row>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(contents: &str) -> Result<Self::Input, ParseError> {
+        Row::parse_lines(contents)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Part1 {
+        input.iter().filter(|x| x.is_safe()).count()
+    }
+
+    fn part2(input: &Self::Input) -> Self::Part2 {
+        input.iter().filter(|x| x.is_safe_dampened()).count()
+    }
+
+    /// `size` reports of 2 to 8 levels, mostly safe with the odd level that
+    /// stays level, turns or jumps.
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        let mut contents = String::new();
+
+        for _ in 0..size {
+            let direction = if rng.one_in(2) { 1 } else { -1 };
+            // Seven steps of at most 4 either way stay within 2..=98
+            let mut level = rng.range(30..=70);
+
+            let mut levels = vec![];
+            for _ in 0..rng.range(2..=8) {
+                levels.push(level);
+                level += if rng.one_in(8) {
+                    rng.range(-4..=4)
+                } else {
+                    direction * rng.range(1..=3)
+                };
+            }
+
+            contents.push_str(&levels.iter().join(" "));
+            contents.push('\n');
+        }
+
+        Some(contents)
+    }
+}
+
+/// Whether any report with one level removed is safe, trying each level in
+/// turn, see `differential`.
+pub(crate) fn brute_force_part2(contents: &str) -> String {
+    let is_safe = |levels: &[i64]| {
+        levels.windows(2).all(|x| (1..=3).contains(&(x[1] - x[0])))
+            || levels.windows(2).all(|x| (1..=3).contains(&(x[0] - x[1])))
+    };
+
+    contents
+        .lines()
+        .filter(|x| {
+            let levels: Vec<i64> = x.split_whitespace().map(|y| y.parse().unwrap()).collect();
+            (0..levels.len()).any(|i| {
+                let mut removed = levels.clone();
+                removed.remove(i);
+                is_safe(&removed)
+            })
+        })
+        .count()
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../inputs/day2.example.txt");
+
+    #[test]
+    fn part1_example() {
+        let input = Day2::parse(EXAMPLE).unwrap();
+        assert_eq!(Day2::part1(&input), 2);
+    }
+
+    #[test]
+    fn part2_example() {
+        let input = Day2::parse(EXAMPLE).unwrap();
+        assert_eq!(Day2::part2(&input), 4);
+    }
+
+    #[test]
+    fn min_removals() {
+        let input = Day2::parse(EXAMPLE).unwrap();
+        let removals: Vec<_> = input.iter().map(|x| x.min_removals()).collect();
+        assert_eq!(removals, [0, 2, 2, 1, 1, 0]);
+
+        assert_eq!(Row(vec![5, 5]).min_removals(), 1);
+        assert_eq!(Row(vec![1, 9, 2, 9, 3, 9, 4]).min_removals(), 3);
+    }
+
+    #[test]
+    fn one_level_report() {
+        let error = Day2::parse("1 2 3\n4\n").unwrap_err();
+        assert_eq!((error.line, error.found.as_str()), (2, "4"));
+        assert_eq!(error.expected, "a report of at least two levels");
+    }
+
+    #[test]
+    fn diagnosis() {
+        let input = Day2::parse(EXAMPLE).unwrap();
+        let diagnoses: String = input.iter().map(|x| Diagnosis(x).to_string()).collect();
+
+        assert_eq!(
+            diagnoses,
+            "\
+7 6 4 2 1  safe
+1 2 7 8 9  unsafe, needs 2 levels removed
+  ^ ^ 2 to 7 changes by 5, more than 3
+9 7 6 2 1  unsafe, needs 2 levels removed
+    ^ ^ 6 to 2 changes by 4, more than 3
+1 3 2 4 5  safe with a level removed
+  ^ ^ 3 to 2 goes down after going up
+  x remove this level
+8 6 4 4 1  safe with a level removed
+    ^ ^ 4 to 4 does not change
+    x remove this level
+1 3 6 7 9  safe
+"
+        );
+
+        let row = Row(vec![10, 13, 17]);
+        assert_eq!(
+            Diagnosis(&row).to_string(),
+            "\
+10 13 17  safe with a level removed
+   ^^ ^^ 13 to 17 changes by 4, more than 3
+      xx remove this level
+"
+        );
+    }
+
+    #[test]
+    fn min_removals_matches_subsets() {
+        let mut rng = Rng::new(3);
+
+        for _ in 0..300 {
+            let levels: Vec<u32> = (0..rng.range(2..=9))
+                .map(|_| rng.range(1..=12) as u32)
+                .collect();
+            let row = Row(levels.clone());
+
+            // The most levels of any safe subsequence, trying all of them
+            let kept = (0_u32..1 << levels.len())
+                .map(|mask| {
+                    let kept: Vec<_> = (0..levels.len())
+                        .filter(|&i| mask & 1 << i != 0)
+                        .map(|i| levels[i] as i64)
+                        .collect();
+                    let safe = kept.windows(2).all(|x| (1..=3).contains(&(x[1] - x[0])))
+                        || kept.windows(2).all(|x| (1..=3).contains(&(x[0] - x[1])));
+                    if safe {
+                        kept.len()
+                    } else {
+                        0
+                    }
+                })
+                .max()
+                .unwrap();
+
+            assert_eq!(row.min_removals(), levels.len() - kept, "{:?}", levels);
+            assert_eq!(
+                row.level_to_remove().is_some(),
+                levels.len() - kept == 1,
+                "{:?}",
+                levels
+            );
+        }
+    }
+}
@@ -1,7 +1,10 @@
 use core::str;
-use std::{fs, io, usize};
 
-struct TokenList(Vec<Token>);
+use crate::{generate::Rng, ParseError, Solution};
+
+pub struct Day3;
+
+pub struct TokenList(Vec<Token>);
 
 enum RunOptions {
     Default,
@@ -24,10 +27,10 @@ impl TokenList {
 
             i += 1;
         }
-        return Self(ret);
+        Self(ret)
     }
 
-    pub fn get_multsum(&self, opts: RunOptions) -> u32 {
+    fn get_multsum(&self, opts: RunOptions) -> u32 {
         let mut is_on = true;
         let mut mults = vec![];
 
@@ -51,7 +54,7 @@ impl TokenList {
             }
         }
 
-        return mults.iter().sum();
+        mults.iter().sum()
     }
 }
 
@@ -80,7 +83,7 @@ impl Token {
 
             return (Some(Self::Mul(pair)), i);
         }
-        return (None, 1);
+        (None, 1)
     }
 }
 
@@ -89,17 +92,13 @@ struct Pair(u32, u32, usize);
 
 impl Pair {
     pub fn parse(contents: &[u8]) -> Option<Pair> {
-        let lhs = Self::parse_num(&contents, b',');
-        if lhs.0.is_none() {
-            return None;
-        }
+        let lhs = Self::parse_num(contents, b',');
+        lhs.0?;
 
         let rhs = Self::parse_num(&contents[lhs.1..], b')');
-        if rhs.0.is_none() {
-            return None;
-        }
+        rhs.0?;
 
-        return Some(Pair(lhs.0?, rhs.0?, 4 + lhs.1 + rhs.1));
+        Some(Pair(lhs.0?, rhs.0?, 4 + lhs.1 + rhs.1))
     }
 
     fn parse_num(contents: &[u8], end: u8) -> (Option<u32>, usize) {
@@ -123,30 +122,96 @@ impl Pair {
             return (None, i);
         }
 
-        return (Some(num.unwrap()), i + 1);
+        (Some(num.unwrap()), i + 1)
     }
 
     pub fn mult(&self) -> u32 {
-        return self.0 * self.1;
+        self.0 * self.1
     }
 }
 
-fn main() -> Result<(), io::Error> {
-    let contents = fs::read_to_string("src/inputs/day3.txt")?;
+impl Solution for Day3 {
+    const DAY: u8 = 3;
+
+    type Input = TokenList;
+    type Part1 = u32;
+    type Part2 = u32;
 
-    let nums = TokenList::parse_string(contents.as_bytes());
+    fn parse(contents: &str) -> Result<Self::Input, ParseError> {
+        Ok(TokenList::parse_string(contents.as_bytes()))
+    }
 
-    // Part 1
-    println!(
-        "Part 1:\nmult sum = {}\n",
-        nums.get_multsum(RunOptions::Default)
-    );
+    fn part1(input: &Self::Input) -> Self::Part1 {
+        input.get_multsum(RunOptions::Default)
+    }
 
-    // Part 2
-    println!(
-        "Part 2:\nmult sum = {}\n",
-        nums.get_multsum(RunOptions::DoDont)
-    );
+    fn part2(input: &Self::Input) -> Self::Part2 {
+        input.get_multsum(RunOptions::DoDont)
+    }
 
-    Ok(())
+    /// `size` instructions, each preceded by some corrupted memory that
+    /// only looks like one.
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        const NOISE: &[&str] = &[
+            "",
+            "",
+            "x",
+            "%",
+            "&",
+            "[",
+            "]",
+            ")",
+            ",",
+            " ",
+            "\n",
+            "mul",
+            "do",
+            "don't",
+            "what()",
+            "select()",
+            "mul[3,7]",
+            "mul(4*",
+            "mul ( 2 , 4 )",
+            "mul(32,64]",
+            "do(",
+        ];
+
+        let mut contents = String::new();
+        for _ in 0..size {
+            contents.push_str(rng.pick::<&str>(NOISE));
+
+            match rng.below(10) {
+                0 => contents.push_str("do()"),
+                1 => contents.push_str("don't()"),
+                _ => contents.push_str(&format!(
+                    "mul({},{})",
+                    rng.range(1..=999),
+                    rng.range(1..=999)
+                )),
+            }
+        }
+        contents.push('\n');
+
+        Some(contents)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../inputs/day3.example.txt");
+    const EXAMPLE2: &str = include_str!("../inputs/day3.example2.txt");
+
+    #[test]
+    fn part1_example() {
+        let input = Day3::parse(EXAMPLE).unwrap();
+        assert_eq!(Day3::part1(&input), 161);
+    }
+
+    #[test]
+    fn part2_example() {
+        let input = Day3::parse(EXAMPLE2).unwrap();
+        assert_eq!(Day3::part2(&input), 48);
+    }
 }
@@ -0,0 +1,180 @@
+use crate::{
+    generate::Rng,
+    geometry::{Direction8, Point},
+    grid::Grid,
+    ParseError, Solution,
+};
+
+pub struct Day4;
+
+struct Pivot {
+    before: String,
+    middle: char,
+    after: String,
+}
+
+fn parse_grid(contents: &str) -> Result<Grid<char>, ParseError> {
+    let grid = Grid::parse(Day4::DAY, contents, |x| x)?;
+
+    if grid.height() <= 1 {
+        return Err(ParseError::new(
+            Day4::DAY,
+            contents,
+            contents.trim(),
+            "a grid of at least two rows",
+        ));
+    }
+
+    Ok(grid)
+}
+
+impl Pivot {
+    fn parse(string: &str) -> Self {
+        assert!(!string.is_empty());
+        assert!(string.len() % 2 == 1);
+
+        let middle = string.chars().count() / 2;
+        let mut chars = string.chars();
+
+        let before = chars.by_ref().take(middle).collect();
+        let middle = chars.next().unwrap();
+        let after = chars.collect();
+
+        Self {
+            before,
+            middle,
+            after,
+        }
+    }
+
+    fn len(&self) -> usize {
+        assert_eq!(self.before.len(), self.after.len());
+
+        self.before.len()
+    }
+
+    fn matches_bidirectional(&self, other: &str) -> bool {
+        let combined = format!("{}{}{}", self.before, self.middle, self.after);
+
+        other == combined || other == combined.chars().rev().collect::<String>()
+    }
+}
+
+fn xmas_scan(grid: &Grid<char>) -> usize {
+    let pivot = Pivot::parse("SAMXMAS");
+
+    grid.find_all(&pivot.middle)
+        .map(|pos| search_around(grid, &pivot, pos))
+        .sum()
+}
+
+fn crossmas_scan(grid: &Grid<char>) -> usize {
+    let pivot = Pivot::parse("MAS");
+
+    grid.find_all(&pivot.middle)
+        .map(|pos| cross_search(grid, &pivot, pos))
+        .sum()
+}
+
+fn cross_search(grid: &Grid<char>, pivot: &Pivot, pos: Point) -> usize {
+    assert!(grid[pos] == pivot.middle);
+
+    let len = pivot.len() as isize;
+
+    // Both diagonals have to read the pivot, in either direction
+    let is_cross = [Direction8::DownRight, Direction8::DownLeft]
+        .iter()
+        .all(|direction| match grid.step(pos, -direction.delta() * len) {
+            Some(start) => {
+                let line: String = grid
+                    .line(start, direction.delta())
+                    .take(2 * pivot.len() + 1)
+                    .map(|(_, x)| x)
+                    .collect();
+
+                pivot.matches_bidirectional(&line)
+            }
+            None => false,
+        });
+
+    if is_cross {
+        1
+    } else {
+        0
+    }
+}
+
+fn search_around(grid: &Grid<char>, pivot: &Pivot, pos: Point) -> usize {
+    assert!(grid[pos] == pivot.middle);
+
+    // Reading outwards from the middle, every direction has to match the
+    // second half of the pivot
+    Direction8::ALL
+        .iter()
+        .filter(|direction| {
+            let rest: String = grid
+                .line(pos, direction.delta())
+                .skip(1)
+                .take(pivot.len())
+                .map(|(_, x)| x)
+                .collect();
+
+            rest == pivot.after
+        })
+        .count()
+}
+
+impl Solution for Day4 {
+    const DAY: u8 = 4;
+
+    type Input = Grid<char>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(contents: &str) -> Result<Self::Input, ParseError> {
+        parse_grid(contents)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Part1 {
+        xmas_scan(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Part2 {
+        crossmas_scan(input)
+    }
+
+    /// A `size` by `size` grid of random letters from `XMAS`, at least two
+    /// rows high.
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        let size = size.max(2);
+
+        let mut contents = String::new();
+        for _ in 0..size {
+            for _ in 0..size {
+                contents.push(*rng.pick(&['X', 'M', 'A', 'S']));
+            }
+            contents.push('\n');
+        }
+
+        Some(contents)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../inputs/day4.example.txt");
+
+    #[test]
+    fn part1_example() {
+        let input = Day4::parse(EXAMPLE).unwrap();
+        assert_eq!(Day4::part1(&input), 18);
+    }
+
+    #[test]
+    fn part2_example() {
+        let input = Day4::parse(EXAMPLE).unwrap();
+        assert_eq!(Day4::part2(&input), 9);
+    }
+}
@@ -0,0 +1,204 @@
+use itertools::Itertools;
+
+use crate::{generate::Rng, parse, ParseError, Solution};
+
+pub struct Day5;
+
+pub struct Run(Vec<Rule>, Vec<Update>);
+struct Rule(u32, u32);
+#[derive(Clone)]
+struct Update(Vec<u32>);
+
+impl Run {
+    pub fn parse(contents: &str) -> Result<Self, ParseError> {
+        let mut lines = contents.trim().lines();
+
+        let rules: Vec<_> = lines.by_ref().take_while(|x| !x.is_empty()).collect();
+        let rules = rules
+            .iter()
+            .map(|x| Rule::parse(contents, x))
+            .collect::<Result<_, _>>()?;
+
+        let updates: Vec<_> = lines.collect();
+        let updates = updates
+            .iter()
+            .map(|x| Update::parse(contents, x))
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self(rules, updates))
+    }
+
+    fn correct_middle_sum(&self) -> u32 {
+        self.1
+            .iter()
+            .filter(|x| x.validate_update(&self.0))
+            .map(|x| x.middle_page())
+            .sum()
+    }
+
+    fn incorrect_corrected_middle_sum(&self) -> u32 {
+        self.1
+            .clone()
+            .iter_mut()
+            .filter(|x| !x.validate_update(&self.0))
+            .map(|x| x.reorder(&self.0).middle_page())
+            .sum()
+    }
+}
+
+impl Rule {
+    pub fn parse(contents: &str, rule: &str) -> Result<Self, ParseError> {
+        let rule = rule.trim();
+        let parts: Vec<_> = rule.split('|').collect();
+        if parts.len() != 2 {
+            return Err(ParseError::new(
+                Day5::DAY,
+                contents,
+                rule,
+                "a rule like `47|53`",
+            ));
+        }
+
+        let first = parse::number(Day5::DAY, contents, parts[0], "a page number")?;
+        let second = parse::number(Day5::DAY, contents, parts[1], "a page number")?;
+
+        Ok(Self(first, second))
+    }
+}
+
+impl Update {
+    pub fn parse(contents: &str, update: &str) -> Result<Self, ParseError> {
+        let update = update.trim();
+        let values: Vec<_> = update
+            .split(',')
+            .map(|x| parse::number(Day5::DAY, contents, x, "a page number"))
+            .collect::<Result<_, _>>()?;
+
+        // The middle page is only defined for an odd number of pages
+        if values.len() % 2 == 0 {
+            return Err(ParseError::new(
+                Day5::DAY,
+                contents,
+                update,
+                "an odd number of pages",
+            ));
+        }
+
+        Ok(Self(values))
+    }
+
+    pub fn validate_update(&self, rules: &[Rule]) -> bool {
+        for i in 0..self.0.len() - 1 {
+            for j in i + 1..self.0.len() {
+                let failed_rules: Vec<_> = rules
+                    .iter()
+                    .filter(|x| self.0[i] == x.1 && self.0[j] == x.0)
+                    .collect();
+
+                if !failed_rules.is_empty() {
+                    return false;
+                }
+            }
+        }
+
+        true
+    }
+
+    pub fn reorder(&mut self, rules: &[Rule]) -> Self {
+        let mut ordered = self.0.clone();
+
+        for i in 0..ordered.len() {
+            for j in 0..ordered[0..ordered.len() - i].len() - 1 {
+                let failed_rules: Vec<_> = rules
+                    .iter()
+                    .filter(|y| ordered[j] == y.1 && ordered[j + 1] == y.0)
+                    .collect();
+
+                if !failed_rules.is_empty() {
+                    ordered.swap(j, j + 1);
+                }
+            }
+        }
+
+        Self(ordered)
+    }
+
+    pub fn middle_page(&self) -> u32 {
+        assert!(!self.0.is_empty());
+        assert!(self.0.len() % 2 == 1);
+        let value = self.0.get(self.0.len() / 2);
+        assert!(value.is_some());
+        value.unwrap().to_owned()
+    }
+}
+
+impl Solution for Day5 {
+    const DAY: u8 = 5;
+
+    type Input = Run;
+    type Part1 = u32;
+    type Part2 = u32;
+
+    fn parse(contents: &str) -> Result<Self::Input, ParseError> {
+        Run::parse(contents)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Part1 {
+        input.correct_middle_sum()
+    }
+
+    fn part2(input: &Self::Input) -> Self::Part2 {
+        input.incorrect_corrected_middle_sum()
+    }
+
+    /// Rules ordering every pair of 49 two digit pages, then `size` updates
+    /// of 1 to 23 pages, about half of them in order.
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        let mut pages: Vec<u32> = (10..100).collect();
+        rng.shuffle(&mut pages);
+        pages.truncate(49);
+
+        let mut rules = vec![];
+        for (i, first) in pages.iter().enumerate() {
+            for second in &pages[i + 1..] {
+                rules.push(format!("{}|{}", first, second));
+            }
+        }
+        rng.shuffle(&mut rules);
+
+        let mut contents = rules.join("\n") + "\n\n";
+        for _ in 0..size {
+            let mut update = pages.clone();
+            rng.shuffle(&mut update);
+            update.truncate(2 * rng.below(12) + 1);
+
+            if rng.one_in(2) {
+                update.sort_by_key(|x| pages.iter().position(|y| y == x));
+            }
+
+            contents.push_str(&update.iter().join(","));
+            contents.push('\n');
+        }
+
+        Some(contents)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../inputs/day5.example.txt");
+
+    #[test]
+    fn part1_example() {
+        let input = Day5::parse(EXAMPLE).unwrap();
+        assert_eq!(Day5::part1(&input), 143);
+    }
+
+    #[test]
+    fn part2_example() {
+        let input = Day5::parse(EXAMPLE).unwrap();
+        assert_eq!(Day5::part2(&input), 123);
+    }
+}
@@ -0,0 +1,333 @@
+use std::sync::{atomic::AtomicUsize, Arc};
+
+use ahash::AHashSet;
+use itertools::Itertools;
+
+use crate::{
+    cancel::Token,
+    generate::Rng,
+    geometry::{Direction, Point},
+    grid::Grid,
+    parallel::prelude::*,
+    parse, ParseError, Solution,
+};
+
+pub struct Day6;
+
+pub struct Lab {
+    grid: Grid<Tile>,
+}
+
+#[derive(Clone, Copy)]
+enum Tile {
+    Obstacle,
+    Nothing,
+}
+
+#[derive(Hash, PartialEq, Eq, Clone, Copy)]
+pub struct State(Point, Direction);
+
+impl Lab {
+    fn from(grid: &Grid<char>) -> Self {
+        Self {
+            grid: grid.map(|&x| Tile::from(x)),
+        }
+    }
+
+    fn with(&self, obstacle: Point) -> Self {
+        let mut grid = self.grid.clone();
+        grid[obstacle] = Tile::Obstacle;
+        Self { grid }
+    }
+
+    pub fn move_until_oob<F>(&self, state: State, mut func: F)
+    where
+        F: FnMut(&State) -> bool,
+    {
+        let mut direction = state.1;
+        let mut state = state;
+        let cancel = Token::current();
+
+        if func(&state) {
+            return;
+        }
+
+        while !cancel.is_cancelled() {
+            let next = match state.get_next(&self.grid, direction) {
+                Some(s) => s,
+                None => {
+                    return;
+                }
+            };
+
+            if self.is_tile_obstacle(next.0) {
+                direction = next.rotate();
+                continue;
+            } else {
+                state = next;
+                if func(&state) {
+                    return;
+                }
+            }
+        }
+    }
+
+    fn is_tile_obstacle(&self, pos: Point) -> bool {
+        match self.grid[pos] {
+            Tile::Obstacle => true,
+            Tile::Nothing => false,
+        }
+    }
+
+    fn count_touched_tiles(&self, state: State) -> usize {
+        let mut touched: AHashSet<Point> = AHashSet::new();
+
+        self.move_until_oob(state, |s| {
+            touched.insert(s.0);
+            false
+        });
+
+        touched.len()
+    }
+
+    fn count_all_loops(&self, state: State) -> usize {
+        let loops = Arc::new(AtomicUsize::new(0));
+
+        let bounds = self.grid.bounds();
+        let placements: Vec<_> = (0..bounds.row)
+            .cartesian_product(0..bounds.column)
+            .map(|(i, j)| Point::new(i, j))
+            .filter(|x| *x != state.0 && !self.is_tile_obstacle(*x))
+            .collect();
+
+        // Workers have no token of their own, so stop placing obstacles once
+        // this part's is cancelled
+        let cancel = Token::current();
+        placements.into_par_iter().for_each(|point| {
+            if cancel.is_cancelled() {
+                return;
+            }
+
+            let grid = self.with(point);
+
+            let mut touched: AHashSet<State> = AHashSet::with_capacity(3000);
+
+            grid.move_until_oob(state, |s| {
+                if !touched.insert(*s) {
+                    loops.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+                    return true;
+                }
+                false
+            });
+        });
+
+        loops.load(std::sync::atomic::Ordering::SeqCst)
+    }
+}
+
+impl Tile {
+    pub fn from(c: char) -> Self {
+        if c == '#' {
+            Self::Obstacle
+        } else {
+            Self::Nothing
+        }
+    }
+}
+
+impl State {
+    fn from(contents: &str, grid: &Grid<char>) -> Result<Self, ParseError> {
+        for (pos, x) in grid.iter() {
+            let direction = match x {
+                '^' => Direction::Up,
+                'v' => Direction::Down,
+                '<' => Direction::Left,
+                '>' => Direction::Right,
+                _ => continue,
+            };
+            return Ok(State(pos, direction));
+        }
+
+        Err(ParseError::new(
+            Day6::DAY,
+            contents,
+            parse::end_of(contents),
+            "a guard (`^`, `v`, `<` or `>`)",
+        ))
+    }
+
+    fn get_next(&self, grid: &Grid<Tile>, direction: Direction) -> Option<Self> {
+        let next = grid.step(self.0, direction.delta())?;
+
+        Some(State(next, direction))
+    }
+
+    fn rotate(&self) -> Direction {
+        self.1.rotate_right()
+    }
+}
+
+impl Solution for Day6 {
+    const DAY: u8 = 6;
+
+    type Input = (Lab, State);
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(contents: &str) -> Result<Self::Input, ParseError> {
+        let grid = Grid::parse(Day6::DAY, contents, |x| x)?;
+        let state = State::from(contents, &grid)?;
+
+        Ok((Lab::from(&grid), state))
+    }
+
+    fn part1((lab, state): &Self::Input) -> Self::Part1 {
+        lab.count_touched_tiles(*state)
+    }
+
+    fn part2((lab, state): &Self::Input) -> Self::Part2 {
+        lab.count_all_loops(*state)
+    }
+
+    /// A `size` by `size` lab with scattered obstacles and a guard, who is
+    /// on the edge a quarter of the time and always walks out eventually.
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        let size = size.max(1);
+
+        for attempt in 0.. {
+            let mut grid = vec![vec!['.'; size]; size];
+            // Give up on obstacles rather than search forever for a layout
+            // the guard gets out of
+            if attempt < 100 {
+                for tile in grid.iter_mut().flatten() {
+                    if rng.one_in(40) {
+                        *tile = '#';
+                    }
+                }
+            }
+
+            let (row, column) = match rng.below(8) {
+                0 => (0, rng.below(size)),
+                1 => (size - 1, rng.below(size)),
+                _ => (rng.below(size), rng.below(size)),
+            };
+            // A guard boxed in on all sides would turn forever
+            for (i, j) in [(row + 1, column), (row, column + 1)]
+                .into_iter()
+                .chain(row.checked_sub(1).map(|x| (x, column)))
+                .chain(column.checked_sub(1).map(|x| (row, x)))
+            {
+                if let Some(tile) = grid.get_mut(i).and_then(|x| x.get_mut(j)) {
+                    *tile = '.';
+                }
+            }
+            grid[row][column] = *rng.pick(&['^', 'v', '<', '>']);
+
+            let contents: String = grid
+                .iter()
+                .map(|x| x.iter().collect::<String>() + "\n")
+                .collect();
+
+            let (lab, state) = Self::parse(&contents).ok()?;
+            let mut seen = AHashSet::new();
+            let mut looped = false;
+            lab.move_until_oob(state, |s| {
+                looped = !seen.insert(*s);
+                looped
+            });
+
+            if !looped {
+                return Some(contents);
+            }
+        }
+
+        None
+    }
+}
+
+/// Tries an obstacle on every free tile but the guard's and walks the whole
+/// route each time, see `differential`.
+pub(crate) fn brute_force_part2(contents: &str) -> String {
+    let mut grid: Vec<Vec<u8>> = contents.lines().map(|x| x.bytes().collect()).collect();
+    let (row, column) = (0..grid.len())
+        .cartesian_product(0..grid[0].len())
+        .find(|&(i, j)| b"^>v<".contains(&grid[i][j]))
+        .unwrap();
+    let facing = b"^>v<"
+        .iter()
+        .position(|&x| x == grid[row][column])
+        .unwrap();
+
+    let mut loops = 0;
+    for (i, j) in (0..grid.len()).cartesian_product(0..grid[0].len()) {
+        if grid[i][j] != b'.' {
+            continue;
+        }
+
+        grid[i][j] = b'#';
+        if walks_in_loop(&grid, (row, column, facing)) {
+            loops += 1;
+        }
+        grid[i][j] = b'.';
+    }
+
+    loops.to_string()
+}
+
+fn walks_in_loop(grid: &[Vec<u8>], start: (usize, usize, usize)) -> bool {
+    const STEPS: [(isize, isize); 4] = [(-1, 0), (0, 1), (1, 0), (0, -1)];
+
+    let (mut row, mut column, mut facing) = start;
+    let mut seen = AHashSet::new();
+    while seen.insert((row, column, facing)) {
+        let (i, j) = (
+            row.checked_add_signed(STEPS[facing].0),
+            column.checked_add_signed(STEPS[facing].1),
+        );
+        match i
+            .zip(j)
+            .filter(|&(i, j)| i < grid.len() && j < grid[i].len())
+        {
+            None => return false,
+            Some((i, j)) if grid[i][j] == b'#' => facing = (facing + 1) % 4,
+            Some((i, j)) => (row, column) = (i, j),
+        }
+    }
+
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../inputs/day6.example.txt");
+
+    #[test]
+    fn part1_example() {
+        let input = Day6::parse(EXAMPLE).unwrap();
+        assert_eq!(Day6::part1(&input), 41);
+    }
+
+    #[test]
+    fn part2_example() {
+        let input = Day6::parse(EXAMPLE).unwrap();
+        assert_eq!(Day6::part2(&input), 6);
+    }
+
+    /// Found by `aoc compare 6`: an obstacle on the guard's starting tile
+    /// used to count as a loop.
+    #[test]
+    fn part2_no_obstacle_on_start() {
+        let input = Day6::parse(
+            "\
+.....v..#..............
+...#..#................
+....#..................
+.....#.................
+",
+        )
+        .unwrap();
+        assert_eq!(Day6::part2(&input), 0);
+    }
+}
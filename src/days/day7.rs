@@ -0,0 +1,224 @@
+use itertools::Itertools;
+
+use crate::{cancel::Token, generate::Rng, parallel::prelude::*, parse, ParseError, Solution};
+
+pub struct Day7;
+
+pub struct Equation {
+    solution: usize,
+    numbers: Vec<usize>,
+}
+
+#[derive(Clone, Copy)]
+enum Operator {
+    Plus,
+    Mult,
+    Concat,
+}
+
+impl Operator {
+    pub fn calculate(&self, lhs: usize, rhs: usize) -> usize {
+        match self {
+            Operator::Plus => lhs + rhs,
+            Operator::Mult => lhs * rhs,
+            Operator::Concat => {
+                let concated = format!("{}{}", lhs, rhs).parse();
+                assert!(concated.is_ok());
+                concated.unwrap()
+            }
+        }
+    }
+}
+
+impl Equation {
+    pub fn parse(contents: &str) -> Result<Vec<Self>, ParseError> {
+        contents
+            .trim()
+            .lines()
+            .map(|x| Equation::from(contents, x))
+            .collect()
+    }
+
+    fn from(contents: &str, line: &str) -> Result<Self, ParseError> {
+        let line = line.trim();
+        let (solution, numbers) = line.split_once(':').ok_or_else(|| {
+            ParseError::new(Day7::DAY, contents, line, "an equation like `190: 10 19`")
+        })?;
+
+        Ok(Self {
+            solution: parse::number(Day7::DAY, contents, solution, "a test value")?,
+            numbers: Self::parse_numbers(contents, numbers)?,
+        })
+    }
+
+    fn parse_numbers(contents: &str, string: &str) -> Result<Vec<usize>, ParseError> {
+        let numbers: Vec<_> = string
+            .split_whitespace()
+            .map(|x| parse::number(Day7::DAY, contents, x, "a number"))
+            .collect::<Result<_, _>>()?;
+
+        if numbers.len() < 2 {
+            return Err(ParseError::new(
+                Day7::DAY,
+                contents,
+                string.trim(),
+                "at least two numbers",
+            ));
+        }
+
+        Ok(numbers)
+    }
+
+    fn find_solution(&self, operators: &[Operator], cancel: &Token) -> usize {
+        let windows: Vec<_> = self.numbers.windows(2).collect();
+
+        let solvable = (0..windows.len())
+            .map(|_| operators.to_vec())
+            .multi_cartesian_product()
+            .take_while(|_| !cancel.is_cancelled())
+            .any(|combo| {
+                let mut sum = windows[0][0];
+                for (i, x) in combo.iter().enumerate() {
+                    sum = x.calculate(sum, windows[i][1]);
+                }
+
+                sum == self.solution
+            });
+
+        if solvable {
+            self.solution
+        } else {
+            0
+        }
+    }
+}
+
+fn total_calibration_result(equations: &[Equation]) -> usize {
+    let ops = vec![Operator::Plus, Operator::Mult];
+    let cancel = Token::current();
+    equations
+        .into_par_iter()
+        .map(|x| x.find_solution(&ops, &cancel))
+        .sum()
+}
+
+fn total_calibration_result2(equations: &[Equation]) -> usize {
+    let ops = vec![Operator::Plus, Operator::Mult, Operator::Concat];
+    let cancel = Token::current();
+    equations
+        .into_par_iter()
+        .map(|x| x.find_solution(&ops, &cancel))
+        .sum()
+}
+
+impl Solution for Day7 {
+    const DAY: u8 = 7;
+
+    type Input = Vec<Equation>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(contents: &str) -> Result<Self::Input, ParseError> {
+        Equation::parse(contents)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Part1 {
+        total_calibration_result(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Part2 {
+        total_calibration_result2(input)
+    }
+
+    /// `size` equations of 2 to 12 numbers, about half of them solvable.
+    /// The numbers have at most 15 digits between them, so no mix of
+    /// operators can overflow, and neither can the total.
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        const OPERATORS: [Operator; 3] = [Operator::Plus, Operator::Mult, Operator::Concat];
+
+        let mut contents = String::new();
+        for _ in 0..size {
+            let count = rng.range(2..=12);
+            let digits = (15 / count).min(3) as u32;
+            let numbers: Vec<_> = (0..count)
+                .map(|_| rng.range(1..=10_i64.pow(digits) - 1) as usize)
+                .collect();
+
+            let result = numbers[1..].iter().fold(numbers[0], |result, &x| {
+                rng.pick(&OPERATORS).calculate(result, x)
+            });
+            let solution = if rng.one_in(2) { result } else { result + 1 };
+
+            contents.push_str(&format!("{}: {}\n", solution, numbers.iter().join(" ")));
+        }
+
+        Some(contents)
+    }
+}
+
+/// Tries every order of `+` and `*` recursively, see `differential`.
+pub(crate) fn brute_force_part1(contents: &str) -> String {
+    brute_force(contents, false)
+}
+
+/// Tries every order of `+`, `*` and `||` recursively, see `differential`.
+pub(crate) fn brute_force_part2(contents: &str) -> String {
+    brute_force(contents, true)
+}
+
+fn brute_force(contents: &str, concat: bool) -> String {
+    fn solvable(target: u64, value: u64, rest: &[u64], concat: bool) -> bool {
+        match rest.split_first() {
+            None => value == target,
+            Some((&x, rest)) => {
+                solvable(target, value + x, rest, concat)
+                    || solvable(target, value * x, rest, concat)
+                    || concat
+                        && solvable(
+                            target,
+                            format!("{}{}", value, x).parse().unwrap(),
+                            rest,
+                            concat,
+                        )
+            }
+        }
+    }
+
+    contents
+        .lines()
+        .map(|x| {
+            let (target, numbers) = x.split_once(':').unwrap();
+            let target = target.parse().unwrap();
+            let numbers: Vec<u64> = numbers
+                .split_whitespace()
+                .map(|y| y.parse().unwrap())
+                .collect();
+
+            if solvable(target, numbers[0], &numbers[1..], concat) {
+                target
+            } else {
+                0
+            }
+        })
+        .sum::<u64>()
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../inputs/day7.example.txt");
+
+    #[test]
+    fn part1_example() {
+        let input = Day7::parse(EXAMPLE).unwrap();
+        assert_eq!(Day7::part1(&input), 3749);
+    }
+
+    #[test]
+    fn part2_example() {
+        let input = Day7::parse(EXAMPLE).unwrap();
+        assert_eq!(Day7::part2(&input), 11387);
+    }
+}
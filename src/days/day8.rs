@@ -0,0 +1,155 @@
+use ahash::{AHashMap, AHashSet};
+
+use crate::{generate::Rng, geometry::Point, grid::Grid, ParseError, Solution};
+
+pub struct Day8;
+
+#[derive(Debug)]
+pub struct Antenae(AHashMap<char, Vec<Point>>);
+
+impl Antenae {
+    fn from(grid: &Grid<char>) -> Self {
+        let mut map = AHashMap::new();
+        for (pos, &x) in grid.iter() {
+            if x == '.' {
+                continue;
+            }
+            map.entry(x)
+                .and_modify(|c: &mut Vec<Point>| c.push(pos))
+                .or_insert(vec![pos]);
+        }
+
+        Self(map)
+    }
+}
+
+fn find_surrounding_antinodes(first: &Point, second: &Point, grid: &Grid<char>) -> Vec<Point> {
+    let step = *second - *first;
+    let mut points = vec![];
+
+    let before = *first - step;
+    if grid.contains(before) {
+        points.push(before);
+    };
+
+    let after = *second + step;
+    if grid.contains(after) {
+        points.push(after);
+    };
+
+    points
+}
+
+fn find_all_inline_antinodes(first: &Point, second: &Point, grid: &Grid<char>) -> Vec<Point> {
+    let step = *second - *first;
+
+    let mut points = vec![*first, *second];
+
+    let mut before = *first - step;
+    while grid.contains(before) {
+        points.push(before);
+        before -= step;
+    }
+
+    let mut after = *second + step;
+    while grid.contains(after) {
+        points.push(after);
+        after += step;
+    }
+
+    points
+}
+
+fn get_point_pairs(points: &[Point]) -> Vec<(Point, Point)> {
+    points
+        .iter()
+        .flat_map(|&x| points.iter().map(move |&y| (x, y)))
+        .filter(|(a, b)| a.column < b.column || (a.column == b.column && a.row < b.row))
+        .collect()
+}
+
+fn iterate_all_pairs<F>(antenae: &Antenae, func: F) -> usize
+where
+    F: Fn(&Point, &Point) -> Vec<Point>,
+{
+    let mut points = AHashSet::new();
+
+    for key in antenae.0.keys() {
+        let items = antenae.0.get(key).unwrap();
+
+        for (first, second) in get_point_pairs(items) {
+            for point in func(&first, &second) {
+                points.insert(point);
+            }
+        }
+    }
+
+    points.len()
+}
+
+impl Solution for Day8 {
+    const DAY: u8 = 8;
+
+    type Input = (Antenae, Grid<char>);
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(contents: &str) -> Result<Self::Input, ParseError> {
+        let grid = Grid::parse(Day8::DAY, contents, |x| x)?;
+
+        Ok((Antenae::from(&grid), grid))
+    }
+
+    fn part1((antenae, grid): &Self::Input) -> Self::Part1 {
+        iterate_all_pairs(antenae, |first, second| {
+            find_surrounding_antinodes(first, second, grid)
+        })
+    }
+
+    fn part2((antenae, grid): &Self::Input) -> Self::Part2 {
+        iterate_all_pairs(antenae, |first, second| {
+            find_all_inline_antinodes(first, second, grid)
+        })
+    }
+
+    /// A `size` by `size` map with an antenna on about one tile in 20,
+    /// sharing up to 8 frequencies.
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        let mut frequencies: Vec<_> = ('0'..='9').chain('a'..='z').chain('A'..='Z').collect();
+        rng.shuffle(&mut frequencies);
+        frequencies.truncate(rng.range(1..=8) as usize);
+
+        let mut contents = String::new();
+        for _ in 0..size.max(1) {
+            for _ in 0..size.max(1) {
+                contents.push(if rng.one_in(20) {
+                    *rng.pick(&frequencies)
+                } else {
+                    '.'
+                });
+            }
+            contents.push('\n');
+        }
+
+        Some(contents)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../inputs/day8.example.txt");
+
+    #[test]
+    fn part1_example() {
+        let input = Day8::parse(EXAMPLE).unwrap();
+        assert_eq!(Day8::part1(&input), 14);
+    }
+
+    #[test]
+    fn part2_example() {
+        let input = Day8::parse(EXAMPLE).unwrap();
+        assert_eq!(Day8::part2(&input), 34);
+    }
+}
@@ -1,8 +1,11 @@
 use core::panic;
-use std::{time, usize};
+
+use crate::{cancel::Token, generate::Rng, ParseError, Solution};
+
+pub struct Day9;
 
 #[derive(Debug, Clone)]
-struct DiscMap {
+pub struct DiscMap {
     map: Vec<Space>,
     count: usize,
 }
@@ -14,12 +17,22 @@ enum Space {
 }
 
 impl DiscMap {
-    fn from(contents: &str) -> Self {
+    fn from(contents: &str) -> Result<Self, ParseError> {
+        let trimmed = contents.trim();
         let mut map = vec![];
         let mut id = 0;
-        for (i, x) in contents.trim().char_indices() {
-            assert!(x.is_numeric());
-            let x = x as usize - '0' as usize;
+        for (i, x) in trimmed.char_indices() {
+            let x = match x.to_digit(10) {
+                Some(x) => x as usize,
+                None => {
+                    return Err(ParseError::new(
+                        Day9::DAY,
+                        contents,
+                        &trimmed[i..i + x.len_utf8()],
+                        "a digit",
+                    ))
+                }
+            };
 
             let item = if i % 2 == 0 {
                 let t = Space::File(id, x);
@@ -34,7 +47,7 @@ impl DiscMap {
 
         let count = map.len();
 
-        Self { map, count }
+        Ok(Self { map, count })
     }
 
     fn flatten(&self) -> Self {
@@ -60,8 +73,9 @@ impl DiscMap {
     fn frag(&mut self) {
         let mut slot = 0;
         let mut num = self.count;
+        let cancel = Token::current();
 
-        loop {
+        while !cancel.is_cancelled() {
             slot = match self.find_slot(slot, 1) {
                 Some(s) => s,
                 None => return,
@@ -82,8 +96,9 @@ impl DiscMap {
 
     fn defrag(&mut self) {
         let mut num_i = self.map.len();
+        let cancel = Token::current();
 
-        loop {
+        while !cancel.is_cancelled() {
             num_i = match self.find_num(num_i) {
                 Some(i) => i,
                 None => return,
@@ -187,8 +202,7 @@ impl DiscMap {
         let mut real_id = 0;
         self.map
             .iter()
-            .enumerate()
-            .flat_map(|(_, x)| match x {
+            .flat_map(|x| match x {
                 Space::File(id, count) => (0..*count)
                     .map(|_| {
                         let res = id * real_id;
@@ -214,28 +228,131 @@ impl Space {
     }
 }
 
-fn main() -> Result<(), std::io::Error> {
-    let contents = std::fs::read_to_string("src/inputs/day9.txt")?;
-    let mut map = DiscMap::from(&contents);
-
-    // Part 1
-    let start = time::Instant::now();
-    let mut flat = map.clone().flatten();
-    flat.frag();
-    println!(
-        "Part 1: checksum = {}, completed in = {:?}\n",
-        flat.checksum(),
-        start.elapsed()
-    );
-
-    // Part 2
-    let start = time::Instant::now();
-    map.defrag();
-    println!(
-        "Part 2: checksum = {}, completed in = {:?}\n",
-        map.checksum(),
-        start.elapsed()
-    );
-
-    Ok(())
+impl Solution for Day9 {
+    const DAY: u8 = 9;
+
+    type Input = DiscMap;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(contents: &str) -> Result<Self::Input, ParseError> {
+        DiscMap::from(contents)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Part1 {
+        let mut flat = input.flatten();
+        flat.frag();
+        flat.checksum()
+    }
+
+    fn part2(input: &Self::Input) -> Self::Part2 {
+        let mut map = input.clone();
+        map.defrag();
+        map.checksum()
+    }
+
+    /// A disk map of `size` files of 1 to 9 blocks, with gaps of 0 to 9
+    /// blocks between them.
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        let mut contents = String::new();
+
+        for i in 0..size.max(1) {
+            if i > 0 {
+                contents.push_str(&rng.range(0..=9).to_string());
+            }
+            contents.push_str(&rng.range(1..=9).to_string());
+        }
+        contents.push('\n');
+
+        Some(contents)
+    }
+}
+
+/// Moves single blocks from the end into the first gap, see `differential`.
+pub(crate) fn brute_force_part1(contents: &str) -> String {
+    let mut blocks = blocks(contents);
+
+    let (mut free, mut file) = (0, blocks.len());
+    loop {
+        while free < blocks.len() && blocks[free].is_some() {
+            free += 1;
+        }
+        while file > 0 && blocks[file - 1].is_none() {
+            file -= 1;
+        }
+        if file == 0 || free >= file - 1 {
+            break;
+        }
+        blocks.swap(free, file - 1);
+    }
+
+    checksum(&blocks)
+}
+
+/// Moves whole files, highest ID first, into the first gap to their left
+/// that fits them, see `differential`.
+pub(crate) fn brute_force_part2(contents: &str) -> String {
+    let mut blocks = blocks(contents);
+    let files = blocks.iter().flatten().max().map_or(0, |x| x + 1);
+
+    for id in (0..files).rev() {
+        let start = match blocks.iter().position(|&x| x == Some(id)) {
+            Some(start) => start,
+            None => continue,
+        };
+        let length = blocks[start..]
+            .iter()
+            .take_while(|&&x| x == Some(id))
+            .count();
+
+        let gap = (0..start)
+            .find(|&i| i + length <= start && blocks[i..i + length].iter().all(|x| x.is_none()));
+        if let Some(gap) = gap {
+            for i in 0..length {
+                blocks.swap(gap + i, start + i);
+            }
+        }
+    }
+
+    checksum(&blocks)
+}
+
+fn blocks(contents: &str) -> Vec<Option<usize>> {
+    contents
+        .trim()
+        .bytes()
+        .enumerate()
+        .flat_map(|(i, x)| {
+            let id = (i % 2 == 0).then_some(i / 2);
+            std::iter::repeat_n(id, (x - b'0') as usize)
+        })
+        .collect()
+}
+
+fn checksum(blocks: &[Option<usize>]) -> String {
+    blocks
+        .iter()
+        .enumerate()
+        .map(|(i, x)| i * x.unwrap_or(0))
+        .sum::<usize>()
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../inputs/day9.example.txt");
+
+    #[test]
+    fn part1_example() {
+        let input = Day9::parse(EXAMPLE).unwrap();
+        assert_eq!(Day9::part1(&input), 1928);
+    }
+
+    #[test]
+    fn part2_example() {
+        let input = Day9::parse(EXAMPLE).unwrap();
+        assert_eq!(Day9::part2(&input), 2858);
+    }
 }
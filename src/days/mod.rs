@@ -0,0 +1,36 @@
+pub mod day1;
+pub mod day10;
+pub mod day11;
+pub mod day14;
+pub mod day15;
+pub mod day2;
+pub mod day3;
+pub mod day4;
+pub mod day5;
+pub mod day6;
+pub mod day7;
+pub mod day8;
+pub mod day9;
+
+use crate::runner::Day;
+
+/// Every implemented day, in order.
+pub static REGISTRY: &[Day] = &[
+    Day::new::<day1::Day1>(),
+    Day::new::<day2::Day2>(),
+    Day::new::<day3::Day3>(),
+    Day::new::<day4::Day4>(),
+    Day::new::<day5::Day5>(),
+    Day::new::<day6::Day6>(),
+    Day::new::<day7::Day7>(),
+    Day::new::<day8::Day8>(),
+    Day::new::<day9::Day9>(),
+    Day::new::<day10::Day10>(),
+    Day::new::<day11::Day11>(),
+    Day::new::<day14::Day14>(),
+    Day::new::<day15::Day15>(),
+];
+
+pub fn find(day: u8) -> Option<&'static Day> {
+    REGISTRY.iter().find(|x| x.day == day)
+}
@@ -0,0 +1,228 @@
+//! Differential testing: checks days against brute force formulations of
+//! their parts on generated inputs, and shrinks any input they disagree on.
+
+use std::fmt::{self, Display};
+
+use crate::{
+    days::{self, day1, day11, day2, day6, day7, day9},
+    runner::Part,
+};
+
+/// A slow but obviously correct way to solve a part, working from the raw
+/// input. It is only ever given inputs the day itself parses.
+pub struct Reference {
+    pub day: u8,
+    pub part: Part,
+    pub solve: fn(&str) -> String,
+}
+
+/// Every part with a brute force reference, in day order.
+pub static REFERENCES: &[Reference] = &[
+    Reference {
+        day: 1,
+        part: Part::Two,
+        solve: day1::brute_force_part2,
+    },
+    Reference {
+        day: 2,
+        part: Part::Two,
+        solve: day2::brute_force_part2,
+    },
+    Reference {
+        day: 6,
+        part: Part::Two,
+        solve: day6::brute_force_part2,
+    },
+    Reference {
+        day: 7,
+        part: Part::One,
+        solve: day7::brute_force_part1,
+    },
+    Reference {
+        day: 7,
+        part: Part::Two,
+        solve: day7::brute_force_part2,
+    },
+    Reference {
+        day: 9,
+        part: Part::One,
+        solve: day9::brute_force_part1,
+    },
+    Reference {
+        day: 9,
+        part: Part::Two,
+        solve: day9::brute_force_part2,
+    },
+    Reference {
+        day: 11,
+        part: Part::One,
+        solve: day11::brute_force_part1,
+    },
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Options {
+    /// Inputs generated for each size.
+    pub seeds: u64,
+    /// Sizes are tried from 1 up to this.
+    pub max_size: usize,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            seeds: 10,
+            max_size: 20,
+        }
+    }
+}
+
+/// An input on which a day and its reference give different answers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Disagreement {
+    pub day: u8,
+    pub part: Part,
+    /// Seed and size of the generated input, before it was shrunk.
+    pub seed: u64,
+    pub size: usize,
+    pub input: String,
+    pub expected: String,
+    /// `None` when the day left the part unsolved.
+    pub actual: Option<String>,
+}
+
+impl Display for Disagreement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "day {} part {} disagrees with its brute force on seed {}, size {}, shrunk to:",
+            self.day, self.part, self.seed, self.size
+        )?;
+        writeln!(f, "{}", self.input.trim_end())?;
+        write!(
+            f,
+            "expected {}, found {}",
+            self.expected,
+            self.actual.as_deref().unwrap_or("unsolved")
+        )
+    }
+}
+
+/// Compares `reference` with its day on generated inputs from the smallest
+/// size up, returning the first disagreement shrunk as far as it goes, or
+/// the number of inputs compared.
+pub fn check(reference: &Reference, options: &Options) -> Result<usize, Disagreement> {
+    let day = days::find(reference.day).expect("references are for registered days");
+    let mut compared = 0;
+
+    for size in 1..=options.max_size {
+        for seed in 0..options.seeds {
+            let input = day
+                .generate(seed, size)
+                .expect("days with references have generators");
+
+            if let Some((expected, actual)) = disagreement(reference, &input) {
+                let input = shrink(input, |x| disagreement(reference, x).is_some());
+                let (expected, actual) =
+                    disagreement(reference, &input).unwrap_or((expected, actual));
+
+                return Err(Disagreement {
+                    day: reference.day,
+                    part: reference.part,
+                    seed,
+                    size,
+                    input,
+                    expected,
+                    actual,
+                });
+            }
+            compared += 1;
+        }
+    }
+
+    Ok(compared)
+}
+
+/// The reference's and the day's answers for `input`, if they differ.
+/// Inputs the day cannot parse never disagree.
+fn disagreement(reference: &Reference, input: &str) -> Option<(String, Option<String>)> {
+    let day = days::find(reference.day)?;
+    let actual = day.run(input, &[reference.part]).ok()?.remove(0).answer;
+    let expected = (reference.solve)(input);
+
+    (actual.as_ref() != Some(&expected)).then_some((expected, actual))
+}
+
+/// Removes lines, then words, then single characters from `input` for as
+/// long as `fails` still holds.
+fn shrink(mut input: String, fails: impl Fn(&str) -> bool) -> String {
+    let separators: [fn(&str) -> Vec<&str>; 3] = [
+        |x| x.split_inclusive('\n').collect(),
+        |x| x.split_inclusive(' ').collect(),
+        |x| {
+            x.char_indices()
+                .map(|(i, c)| &x[i..i + c.len_utf8()])
+                .collect()
+        },
+    ];
+
+    for split in separators {
+        let mut i = 0;
+        loop {
+            let pieces = split(&input);
+            if i >= pieces.len() {
+                break;
+            }
+
+            let candidate: String = pieces
+                .iter()
+                .enumerate()
+                .filter(|&(j, _)| j != i)
+                .map(|(_, x)| *x)
+                .collect();
+            if fails(&candidate) {
+                input = candidate;
+            } else {
+                i += 1;
+            }
+        }
+    }
+
+    input
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn references_agree() {
+        for reference in REFERENCES {
+            // Day 6 once only went wrong from seed 10, size 23, so sweep that
+            // far where the brute force is cheap. Those for days 7 and 11
+            // grow exponentially with the size.
+            let options = match reference.day {
+                7 | 11 => Options {
+                    seeds: 4,
+                    max_size: 6,
+                },
+                _ => Options {
+                    seeds: 12,
+                    max_size: 24,
+                },
+            };
+
+            if let Err(disagreement) = check(reference, &options) {
+                panic!("{}", disagreement);
+            }
+        }
+    }
+
+    #[test]
+    fn shrink_to_smallest() {
+        let input = String::from("1 2\n3 4 5\n6\n");
+
+        assert_eq!(shrink(input.clone(), |x| x.contains('4')), "4");
+        assert_eq!(shrink(input, |x| x.contains("3 4")), "3 4");
+    }
+}
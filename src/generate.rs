@@ -0,0 +1,94 @@
+//! Seeded random inputs for stress testing and profiling days on inputs
+//! other than our own.
+
+use std::ops::RangeInclusive;
+
+/// Default for `--size`, see each day's `Solution::generate` for what it
+/// counts.
+pub const DEFAULT_SIZE: usize = 100;
+
+/// SplitMix64, which is small, fast and good enough for test data. The same
+/// seed gives the same sequence on every platform and release.
+#[derive(Debug, Clone)]
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Self(seed)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e3779b97f4a7c15);
+
+        let mut x = self.0;
+        x = (x ^ (x >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        x = (x ^ (x >> 27)).wrapping_mul(0x94d049bb133111eb);
+        x ^ (x >> 31)
+    }
+
+    /// A value in `0..bound`, which must not be empty.
+    pub fn below(&mut self, bound: usize) -> usize {
+        assert!(bound > 0);
+
+        // Multiplying keeps the high bits, so the bias is at most
+        // `bound / 2^64`
+        ((self.next_u64() as u128 * bound as u128) >> 64) as usize
+    }
+
+    pub fn range(&mut self, range: RangeInclusive<i64>) -> i64 {
+        let (start, end) = range.into_inner();
+        assert!(start <= end);
+
+        start + self.below((end - start) as usize + 1) as i64
+    }
+
+    /// True with a chance of one in `n`.
+    pub fn one_in(&mut self, n: usize) -> bool {
+        self.below(n) == 0
+    }
+
+    pub fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.below(items.len())]
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.below(i + 1));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{days, runner::Part};
+
+    #[test]
+    fn deterministic() {
+        let mut first = Rng::new(7);
+        let mut second = Rng::new(7);
+
+        let values: Vec<_> = (0..100).map(|_| first.range(-3..=3)).collect();
+        assert_eq!(
+            values,
+            (0..100).map(|_| second.range(-3..=3)).collect::<Vec<_>>()
+        );
+        assert!(values.iter().all(|x| (-3..=3).contains(x)));
+        assert!((-3..=3).all(|x| values.contains(&x)));
+    }
+
+    #[test]
+    fn every_day_generates_valid_input() {
+        for day in days::REGISTRY {
+            for seed in 0..4 {
+                let contents = day
+                    .generate(seed, 20)
+                    .unwrap_or_else(|| panic!("day {} has no generator", day.day));
+
+                if let Err(error) = day.run(&contents, &Part::ALL) {
+                    panic!("day {} seed {}: {}\n{}", day.day, seed, error, contents);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,308 @@
+//! Points and directions shared by the grid puzzles.
+//!
+//! Everything is addressed by `row` and `column`, with rows growing
+//! downwards and columns growing to the right, so `Direction::Up` is
+//! `(-1, 0)`. Puzzles that talk about `x` and `y` map `x` to the column and
+//! `y` to the row.
+
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+/// A signed position, or the offset between two positions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Point {
+    pub row: isize,
+    pub column: isize,
+}
+
+impl Point {
+    pub const ORIGIN: Self = Self::new(0, 0);
+
+    pub const fn new(row: isize, column: isize) -> Self {
+        Self { row, column }
+    }
+
+    pub fn manhattan(&self, other: &Self) -> usize {
+        self.row.abs_diff(other.row) + self.column.abs_diff(other.column)
+    }
+
+    pub fn chebyshev(&self, other: &Self) -> usize {
+        self.row
+            .abs_diff(other.row)
+            .max(self.column.abs_diff(other.column))
+    }
+
+    /// Whether the point lies in the rectangle from the origin up to, but not
+    /// including, `bounds`.
+    pub fn within(&self, bounds: &Self) -> bool {
+        (0..bounds.row).contains(&self.row) && (0..bounds.column).contains(&self.column)
+    }
+
+    /// Steps by `delta`, or `None` when that leaves `bounds`.
+    pub fn step_within(&self, delta: Self, bounds: &Self) -> Option<Self> {
+        let next = *self + delta;
+        next.within(bounds).then_some(next)
+    }
+
+    /// Wraps the point around the edges of `bounds`, as on a torus.
+    pub fn wrap(&self, bounds: &Self) -> Self {
+        Self::new(
+            self.row.rem_euclid(bounds.row),
+            self.column.rem_euclid(bounds.column),
+        )
+    }
+}
+
+impl Add for Point {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self::new(self.row + rhs.row, self.column + rhs.column)
+    }
+}
+
+impl AddAssign for Point {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sub for Point {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self::new(self.row - rhs.row, self.column - rhs.column)
+    }
+}
+
+impl SubAssign for Point {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl Neg for Point {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self::new(-self.row, -self.column)
+    }
+}
+
+impl Mul<isize> for Point {
+    type Output = Self;
+
+    fn mul(self, rhs: isize) -> Self::Output {
+        Self::new(self.row * rhs, self.column * rhs)
+    }
+}
+
+/// One of the four orthogonal directions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Direction {
+    /// Clockwise from up.
+    pub const ALL: [Self; 4] = [Self::Up, Self::Right, Self::Down, Self::Left];
+
+    /// Turns 90 degrees clockwise.
+    pub fn rotate_right(&self) -> Self {
+        match self {
+            Self::Up => Self::Right,
+            Self::Right => Self::Down,
+            Self::Down => Self::Left,
+            Self::Left => Self::Up,
+        }
+    }
+
+    /// Turns 90 degrees counter-clockwise.
+    pub fn rotate_left(&self) -> Self {
+        self.reverse().rotate_right()
+    }
+
+    pub fn reverse(&self) -> Self {
+        self.rotate_right().rotate_right()
+    }
+
+    pub fn delta(&self) -> Point {
+        match self {
+            Self::Up => Point::new(-1, 0),
+            Self::Right => Point::new(0, 1),
+            Self::Down => Point::new(1, 0),
+            Self::Left => Point::new(0, -1),
+        }
+    }
+}
+
+/// One of the four orthogonal or four diagonal directions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction8 {
+    Up,
+    UpRight,
+    Right,
+    DownRight,
+    Down,
+    DownLeft,
+    Left,
+    UpLeft,
+}
+
+impl Direction8 {
+    /// Clockwise from up.
+    pub const ALL: [Self; 8] = [
+        Self::Up,
+        Self::UpRight,
+        Self::Right,
+        Self::DownRight,
+        Self::Down,
+        Self::DownLeft,
+        Self::Left,
+        Self::UpLeft,
+    ];
+
+    /// Turns 45 degrees clockwise.
+    pub fn rotate_right(&self) -> Self {
+        Self::ALL[(self.index() + 1) % 8]
+    }
+
+    /// Turns 45 degrees counter-clockwise.
+    pub fn rotate_left(&self) -> Self {
+        Self::ALL[(self.index() + 7) % 8]
+    }
+
+    pub fn reverse(&self) -> Self {
+        Self::ALL[(self.index() + 4) % 8]
+    }
+
+    pub fn delta(&self) -> Point {
+        match self {
+            Self::Up => Point::new(-1, 0),
+            Self::UpRight => Point::new(-1, 1),
+            Self::Right => Point::new(0, 1),
+            Self::DownRight => Point::new(1, 1),
+            Self::Down => Point::new(1, 0),
+            Self::DownLeft => Point::new(1, -1),
+            Self::Left => Point::new(0, -1),
+            Self::UpLeft => Point::new(-1, -1),
+        }
+    }
+
+    fn index(&self) -> usize {
+        *self as usize
+    }
+}
+
+impl From<Direction> for Direction8 {
+    fn from(direction: Direction) -> Self {
+        match direction {
+            Direction::Up => Self::Up,
+            Direction::Right => Self::Right,
+            Direction::Down => Self::Down,
+            Direction::Left => Self::Left,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rotate() {
+        for direction in Direction::ALL {
+            assert_eq!(direction.rotate_right().rotate_left(), direction);
+            assert_eq!(direction.reverse().reverse(), direction);
+            assert_eq!(direction.reverse().delta(), -direction.delta());
+
+            // Clockwise, with rows growing downwards
+            let Point { row, column } = direction.delta();
+            assert_eq!(direction.rotate_right().delta(), Point::new(column, -row));
+            assert_eq!(direction.rotate_left().delta(), Point::new(-column, row));
+        }
+        assert_eq!(Direction::Up.rotate_right(), Direction::Right);
+        assert_eq!(Direction::Up.rotate_left(), Direction::Left);
+
+        for direction in Direction8::ALL {
+            assert_eq!(direction.rotate_right().rotate_left(), direction);
+            assert_eq!(direction.reverse().delta(), -direction.delta());
+            let mut turned = direction;
+            for _ in 0..4 {
+                turned = turned.rotate_left();
+            }
+            assert_eq!(turned, direction.reverse());
+        }
+        assert_eq!(Direction8::UpLeft.rotate_right(), Direction8::Up);
+        assert_eq!(Direction8::Up.rotate_left(), Direction8::UpLeft);
+    }
+
+    #[test]
+    fn direction8_order() {
+        // `index` relies on `ALL` listing the variants in declaration order
+        for (i, direction) in Direction8::ALL.iter().enumerate() {
+            assert_eq!(direction.index(), i);
+        }
+
+        // Every step round `ALL` turns 45 degrees clockwise, which with rows
+        // growing downwards makes the cross product negative, and orthogonal
+        // and diagonal directions alternate
+        for (i, pair) in Direction8::ALL.windows(2).enumerate() {
+            let (a, b) = (pair[0].delta(), pair[1].delta());
+            assert_eq!(a.row * b.column - a.column * b.row, -1);
+            assert_eq!(a.manhattan(&Point::ORIGIN), 1 + i % 2);
+        }
+
+        for direction in Direction::ALL {
+            assert_eq!(Direction8::from(direction).delta(), direction.delta());
+            assert_eq!(
+                Direction8::from(direction.rotate_right()),
+                Direction8::from(direction).rotate_right().rotate_right()
+            );
+        }
+    }
+
+    #[test]
+    fn distances() {
+        let a = Point::new(2, -3);
+        let b = Point::new(-1, 4);
+        assert_eq!(a.manhattan(&b), 10);
+        assert_eq!(a.chebyshev(&b), 7);
+        assert_eq!(b.manhattan(&a), 10);
+        assert_eq!(a.manhattan(&a), 0);
+        assert_eq!(Point::ORIGIN.chebyshev(&Point::new(-5, 5)), 5);
+    }
+
+    #[test]
+    fn step_within() {
+        let bounds = Point::new(3, 4);
+        let corner = Point::new(2, 3);
+        assert_eq!(
+            corner.step_within(Direction::Left.delta(), &bounds),
+            Some(Point::new(2, 2))
+        );
+        assert_eq!(corner.step_within(Direction::Right.delta(), &bounds), None);
+        assert_eq!(corner.step_within(Direction::Down.delta(), &bounds), None);
+        assert_eq!(
+            Point::ORIGIN.step_within(Direction::Up.delta(), &bounds),
+            None
+        );
+        assert_eq!(Point::ORIGIN.step_within(corner, &bounds), Some(corner));
+        assert_eq!(
+            Point::ORIGIN.step_within(Point::ORIGIN, &Point::ORIGIN),
+            None
+        );
+    }
+
+    #[test]
+    fn wrap() {
+        let bounds = Point::new(7, 11);
+        assert_eq!(Point::new(3, 5).wrap(&bounds), Point::new(3, 5));
+        assert_eq!(Point::new(7, 11).wrap(&bounds), Point::ORIGIN);
+        assert_eq!(Point::new(-1, -1).wrap(&bounds), Point::new(6, 10));
+        assert_eq!(Point::new(-7, -12).wrap(&bounds), Point::new(0, 10));
+        assert_eq!(Point::new(-15, 23).wrap(&bounds), Point::new(6, 1));
+    }
+}
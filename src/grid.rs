@@ -0,0 +1,381 @@
+use std::{
+    fmt::{self, Display},
+    ops::{Index, IndexMut},
+};
+
+use crate::{
+    geometry::{Direction, Direction8, Point},
+    ParseError,
+};
+
+/// Rectangular grid stored row by row in a single `Vec`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    width: usize,
+    height: usize,
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, cells: Vec<T>) -> Self {
+        assert_eq!(cells.len(), width * height);
+
+        Self {
+            cells,
+            width,
+            height,
+        }
+    }
+
+    /// Parses one row per line, mapping every character with `func`. Rows
+    /// are trimmed and must all be the same width.
+    pub fn parse<F>(day: u8, contents: &str, mut func: F) -> Result<Self, ParseError>
+    where
+        F: FnMut(char) -> T,
+    {
+        let lines: Vec<_> = contents.trim().lines().map(|x| x.trim()).collect();
+
+        let width = lines.first().map_or(0, |x| x.chars().count());
+        if width == 0 {
+            return Err(ParseError::new(
+                day,
+                contents,
+                contents.trim(),
+                "a grid of at least one cell",
+            ));
+        }
+
+        let mut cells = Vec::with_capacity(width * lines.len());
+        for line in lines.iter() {
+            if line.chars().count() != width {
+                return Err(ParseError::new(
+                    day,
+                    contents,
+                    line,
+                    format!("a row of {} cells", width),
+                ));
+            }
+
+            cells.extend(line.chars().map(&mut func));
+        }
+
+        Ok(Self::new(width, lines.len(), cells))
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// One past the last row and column, for use with `Point::within`.
+    pub fn bounds(&self) -> Point {
+        Point::new(self.height as isize, self.width as isize)
+    }
+
+    pub fn contains(&self, pos: Point) -> bool {
+        pos.within(&self.bounds())
+    }
+
+    /// The position `delta` away from `pos`, if it lies inside the grid.
+    pub fn step(&self, pos: Point, delta: Point) -> Option<Point> {
+        pos.step_within(delta, &self.bounds())
+    }
+
+    pub fn get(&self, pos: Point) -> Option<&T> {
+        if self.contains(pos) {
+            Some(&self.cells[self.index_of(pos)])
+        } else {
+            None
+        }
+    }
+
+    /// The cell at `pos` without checking that it lies inside the grid.
+    ///
+    /// # Safety
+    ///
+    /// `pos` must be inside the grid, as `contains` checks.
+    pub unsafe fn get_unchecked(&self, pos: Point) -> &T {
+        debug_assert!(self.contains(pos), "{:?} is outside the grid", pos);
+        self.cells.get_unchecked(self.index_of(pos))
+    }
+
+    pub fn get_mut(&mut self, pos: Point) -> Option<&mut T> {
+        if self.contains(pos) {
+            let index = self.index_of(pos);
+            Some(&mut self.cells[index])
+        } else {
+            None
+        }
+    }
+
+    /// The cell at `pos` without checking that it lies inside the grid.
+    ///
+    /// # Safety
+    ///
+    /// `pos` must be inside the grid, as `contains` checks.
+    pub unsafe fn get_unchecked_mut(&mut self, pos: Point) -> &mut T {
+        debug_assert!(self.contains(pos), "{:?} is outside the grid", pos);
+        let index = self.index_of(pos);
+        self.cells.get_unchecked_mut(index)
+    }
+
+    /// The up to four orthogonal neighbours of `pos`.
+    pub fn neighbours4(&self, pos: Point) -> impl Iterator<Item = Point> + '_ {
+        Direction::ALL
+            .iter()
+            .filter_map(move |x| self.step(pos, x.delta()))
+    }
+
+    /// The up to eight orthogonal and diagonal neighbours of `pos`.
+    pub fn neighbours8(&self, pos: Point) -> impl Iterator<Item = Point> + '_ {
+        Direction8::ALL
+            .iter()
+            .filter_map(move |x| self.step(pos, x.delta()))
+    }
+
+    /// Walks from `start` (inclusive) in steps of `delta` until leaving the
+    /// grid, which covers rows, columns and diagonals alike.
+    pub fn line(&self, start: Point, delta: Point) -> impl Iterator<Item = (Point, &T)> {
+        let mut next = self.contains(start).then_some(start);
+
+        std::iter::from_fn(move || {
+            let pos = next?;
+            next = self.step(pos, delta);
+            Some((pos, &self[pos]))
+        })
+    }
+
+    pub fn row(&self, row: usize) -> &[T] {
+        &self.cells[row * self.width..(row + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.width)
+    }
+
+    pub fn column(&self, column: usize) -> impl Iterator<Item = &T> {
+        self.line(Point::new(0, column as isize), Direction::Down.delta())
+            .map(|(_, x)| x)
+    }
+
+    /// Every cell with its position, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.cells.iter().enumerate().map(|(i, x)| {
+            let pos = Point::new((i / self.width) as isize, (i % self.width) as isize);
+            (pos, x)
+        })
+    }
+
+    pub fn map<U, F>(&self, func: F) -> Grid<U>
+    where
+        F: FnMut(&T) -> U,
+    {
+        Grid::new(
+            self.width,
+            self.height,
+            self.cells.iter().map(func).collect(),
+        )
+    }
+
+    fn index_of(&self, pos: Point) -> usize {
+        pos.row as usize * self.width + pos.column as usize
+    }
+}
+
+impl<T: PartialEq> Grid<T> {
+    /// Position of the first cell equal to `value`, row by row.
+    pub fn find(&self, value: &T) -> Option<Point> {
+        self.find_all(value).next()
+    }
+
+    pub fn find_all<'a>(&'a self, value: &'a T) -> impl Iterator<Item = Point> + 'a {
+        self.iter()
+            .filter(move |(_, x)| *x == value)
+            .map(|(x, _)| x)
+    }
+}
+
+/// Panics when `pos` is outside the grid; use `get` to check first.
+impl<T> Index<Point> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Point) -> &Self::Output {
+        assert!(self.contains(pos), "{:?} is outside the grid", pos);
+        &self.cells[self.index_of(pos)]
+    }
+}
+
+impl<T> IndexMut<Point> for Grid<T> {
+    fn index_mut(&mut self, pos: Point) -> &mut Self::Output {
+        assert!(self.contains(pos), "{:?} is outside the grid", pos);
+        let index = self.index_of(pos);
+        &mut self.cells[index]
+    }
+}
+
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.rows() {
+            for x in row {
+                write!(f, "{}", x)?;
+            }
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "abc\ndef\nghi\njkl\n";
+
+    fn example() -> Grid<char> {
+        Grid::parse(0, EXAMPLE, |x| x).unwrap()
+    }
+
+    #[test]
+    fn parse() {
+        let grid = example();
+        assert_eq!((grid.width(), grid.height()), (3, 4));
+        assert_eq!(grid.bounds(), Point::new(4, 3));
+        assert_eq!(grid.row(1), ['d', 'e', 'f']);
+
+        let digits = Grid::parse(0, "  12\n  34  \n\n", |x| x.to_digit(10).unwrap()).unwrap();
+        assert_eq!(digits.rows().collect::<Vec<_>>(), [[1, 2], [3, 4]]);
+    }
+
+    #[test]
+    fn parse_errors() {
+        let contents = "abc\nde\nfgh\n";
+        let error = Grid::parse(4, contents, |x| x).unwrap_err();
+        assert_eq!((error.line, error.column), (2, 1));
+        assert_eq!(error.found, "de");
+        assert_eq!(error.expected, "a row of 3 cells");
+
+        let error = Grid::parse(4, "abc\nabcd", |x| x).unwrap_err();
+        assert_eq!((error.line, error.found.as_str()), (2, "abcd"));
+
+        for contents in ["", "\n  \n"] {
+            let error = Grid::parse(4, contents, |x| x).unwrap_err();
+            assert_eq!(error.expected, "a grid of at least one cell");
+        }
+    }
+
+    #[test]
+    fn get_and_step_at_the_edges() {
+        let mut grid = example();
+        assert_eq!(grid.get(Point::new(0, 0)), Some(&'a'));
+        assert_eq!(grid.get(Point::new(3, 2)), Some(&'l'));
+        for pos in [(-1, 0), (0, -1), (4, 0), (0, 3), (4, 3)] {
+            let pos = Point::new(pos.0, pos.1);
+            assert_eq!(grid.get(pos), None);
+            assert_eq!(grid.get_mut(pos), None);
+        }
+
+        *grid.get_mut(Point::new(3, 2)).unwrap() = 'L';
+        grid[Point::new(0, 0)] = 'A';
+        assert_eq!(grid[Point::new(3, 2)], 'L');
+        assert_eq!(unsafe { *grid.get_unchecked(Point::new(0, 0)) }, 'A');
+        unsafe { *grid.get_unchecked_mut(Point::new(1, 1)) = 'E' };
+        assert_eq!(grid[Point::new(1, 1)], 'E');
+
+        let corner = Point::new(3, 2);
+        assert_eq!(
+            grid.step(corner, Direction::Up.delta()),
+            Some(Point::new(2, 2))
+        );
+        assert_eq!(grid.step(corner, Direction::Right.delta()), None);
+        assert_eq!(grid.step(corner, Direction::Down.delta()), None);
+        assert_eq!(grid.step(Point::ORIGIN, Point::new(3, 2)), Some(corner));
+        assert_eq!(grid.step(Point::ORIGIN, Point::new(4, 0)), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "outside the grid")]
+    fn index_outside() {
+        let _ = example()[Point::new(0, 3)];
+    }
+
+    #[test]
+    fn neighbours_at_corners() {
+        let grid = example();
+        let neighbours4 = |pos| grid.neighbours4(pos).collect::<Vec<_>>();
+        let neighbours8 = |pos| grid.neighbours8(pos).collect::<Vec<_>>();
+
+        assert_eq!(
+            neighbours4(Point::ORIGIN),
+            [Point::new(0, 1), Point::new(1, 0)]
+        );
+        assert_eq!(
+            neighbours8(Point::ORIGIN),
+            [Point::new(0, 1), Point::new(1, 1), Point::new(1, 0)]
+        );
+        assert_eq!(
+            neighbours8(Point::new(3, 2)),
+            [Point::new(2, 2), Point::new(3, 1), Point::new(2, 1)]
+        );
+        assert_eq!(neighbours4(Point::new(1, 1)).len(), 4);
+        assert_eq!(neighbours8(Point::new(1, 1)).len(), 8);
+
+        let single = Grid::new(1, 1, vec![0]);
+        assert_eq!(single.neighbours8(Point::ORIGIN).count(), 0);
+    }
+
+    #[test]
+    fn lines() {
+        let grid = example();
+        let line = |start: Point, direction: Direction8| {
+            grid.line(start, direction.delta())
+                .map(|(_, &x)| x)
+                .collect::<String>()
+        };
+
+        assert_eq!(line(Point::new(1, 0), Direction8::Right), "def");
+        assert_eq!(line(Point::new(3, 1), Direction8::Up), "kheb");
+        assert_eq!(line(Point::ORIGIN, Direction8::DownRight), "aei");
+        assert_eq!(line(Point::new(3, 0), Direction8::UpRight), "jhf");
+        assert_eq!(line(Point::new(0, 2), Direction8::Right), "c");
+        assert_eq!(line(Point::new(0, 3), Direction8::Left), "");
+
+        let positions: Vec<_> = grid
+            .line(Point::new(2, 2), Direction8::UpLeft.delta())
+            .map(|(x, _)| x)
+            .collect();
+        assert_eq!(
+            positions,
+            [Point::new(2, 2), Point::new(1, 1), Point::ORIGIN]
+        );
+
+        assert_eq!(grid.column(1).collect::<String>(), "behk");
+    }
+
+    #[test]
+    fn find() {
+        let grid = Grid::parse(0, ".#.\n..#\n#..", |x| x).unwrap();
+        assert_eq!(grid.find(&'#'), Some(Point::new(0, 1)));
+        assert_eq!(grid.find(&'x'), None);
+        assert_eq!(
+            grid.find_all(&'#').collect::<Vec<_>>(),
+            [Point::new(0, 1), Point::new(1, 2), Point::new(2, 0)]
+        );
+        assert_eq!(grid.find_all(&'x').count(), 0);
+    }
+
+    #[test]
+    fn display() {
+        let grid = example();
+        assert_eq!(grid.to_string(), EXAMPLE);
+        assert_eq!(
+            grid.map(|&x| x == 'e')
+                .map(|&x| if x { '#' } else { '.' })
+                .to_string(),
+            "...\n.#.\n...\n...\n"
+        );
+    }
+}
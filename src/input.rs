@@ -0,0 +1,387 @@
+use std::{
+    env,
+    error::Error,
+    fmt::{self, Display},
+    fs::{self, File},
+    io::{self, BufRead, BufReader, Read},
+    path::{Path, PathBuf},
+};
+
+/// Environment variable naming an extra directory to look for inputs in.
+pub const INPUT_DIR_VAR: &str = "AOC_INPUT_DIR";
+
+/// Where a day's puzzle input is read from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Source {
+    /// `dayN.txt`, or `dayN.<variant>.txt` when a variant such as `example`
+    /// is given, looked up in each of the input directories in turn.
+    Named(Option<String>),
+    Path(PathBuf),
+    Stdin,
+}
+
+impl Default for Source {
+    fn default() -> Self {
+        Self::Named(None)
+    }
+}
+
+impl Source {
+    /// Interprets a command line argument, where `-` means stdin.
+    pub fn from_arg(arg: &str) -> Self {
+        if arg == "-" {
+            Self::Stdin
+        } else {
+            Self::Path(PathBuf::from(arg))
+        }
+    }
+
+    pub fn read(&self, day: u8) -> Result<String, InputError> {
+        match self {
+            Source::Named(variant) => Self::read_named(day, variant.as_deref()),
+            Source::Path(path) => fs::read_to_string(path).map_err(|error| match error.kind() {
+                io::ErrorKind::NotFound => InputError::NotFound {
+                    day,
+                    variant: None,
+                    tried: vec![path.clone()],
+                },
+                _ => InputError::Io {
+                    path: Some(path.clone()),
+                    error,
+                },
+            }),
+            Source::Stdin => {
+                let mut contents = String::new();
+                io::stdin()
+                    .read_to_string(&mut contents)
+                    .map_err(|error| InputError::Io { path: None, error })?;
+                Ok(contents)
+            }
+        }
+    }
+
+    /// Opens the input for reading a line at a time, for inputs too large to
+    /// hold in memory.
+    pub fn open(&self, day: u8) -> Result<Box<dyn BufRead>, InputError> {
+        match self {
+            Source::Named(variant) => {
+                let (_, file) = Self::open_named(day, variant.as_deref())?;
+                Ok(Box::new(BufReader::new(file)))
+            }
+            Source::Path(path) => match File::open(path) {
+                Ok(file) => Ok(Box::new(BufReader::new(file))),
+                Err(error) if error.kind() == io::ErrorKind::NotFound => {
+                    Err(InputError::NotFound {
+                        day,
+                        variant: None,
+                        tried: vec![path.clone()],
+                    })
+                }
+                Err(error) => Err(InputError::Io {
+                    path: Some(path.clone()),
+                    error,
+                }),
+            },
+            Source::Stdin => Ok(Box::new(io::stdin().lock())),
+        }
+    }
+
+    /// Files the input could be read from, in the order they are tried.
+    /// Empty for stdin.
+    pub fn paths(&self, day: u8) -> Vec<PathBuf> {
+        match self {
+            Source::Named(variant) => {
+                let name = file_name(day, variant.as_deref());
+                input_dirs().into_iter().map(|x| x.join(&name)).collect()
+            }
+            Source::Path(path) => vec![path.clone()],
+            Source::Stdin => vec![],
+        }
+    }
+
+    fn read_named(day: u8, variant: Option<&str>) -> Result<String, InputError> {
+        let (path, mut file) = Self::open_named(day, variant)?;
+
+        let mut contents = String::new();
+        file.read_to_string(&mut contents)
+            .map_err(|error| InputError::Io {
+                path: Some(path),
+                error,
+            })?;
+        Ok(contents)
+    }
+
+    fn open_named(day: u8, variant: Option<&str>) -> Result<(PathBuf, File), InputError> {
+        open_first(&input_dirs(), day, variant)
+    }
+}
+
+/// Opens the named input in the first of `dirs` that has it.
+fn open_first(
+    dirs: &[PathBuf],
+    day: u8,
+    variant: Option<&str>,
+) -> Result<(PathBuf, File), InputError> {
+    let name = file_name(day, variant);
+    let mut tried = vec![];
+
+    for dir in dirs {
+        let path = dir.join(&name);
+
+        match File::open(&path) {
+            Ok(file) => return Ok((path, file)),
+            Err(error) if error.kind() == io::ErrorKind::NotFound => tried.push(path),
+            Err(error) => {
+                return Err(InputError::Io {
+                    path: Some(path),
+                    error,
+                })
+            }
+        }
+    }
+
+    Err(InputError::NotFound {
+        day,
+        variant: variant.map(String::from),
+        tried,
+    })
+}
+
+pub fn file_name(day: u8, variant: Option<&str>) -> String {
+    match variant {
+        Some(variant) => format!("day{}.{}.txt", day, variant),
+        None => format!("day{}.txt", day),
+    }
+}
+
+/// FNV-1a hash of an input as 16 hex digits. Trailing whitespace is ignored
+/// and the result is stable across platforms and releases, so it can be
+/// stored next to an answer.
+pub fn hash(contents: &str) -> String {
+    let hash = contents
+        .trim_end()
+        .bytes()
+        .fold(0xcbf29ce484222325_u64, |hash, x| {
+            (hash ^ x as u64).wrapping_mul(0x100000001b3)
+        });
+
+    format!("{:016x}", hash)
+}
+
+/// Directories searched for named inputs, in order: `$AOC_INPUT_DIR`,
+/// `src/inputs` relative to the working directory, then the crate's own
+/// `src/inputs` so the runner works from anywhere.
+pub fn input_dirs() -> Vec<PathBuf> {
+    search_dirs(
+        env::var_os(INPUT_DIR_VAR).map(PathBuf::from),
+        env::current_dir().ok().as_deref(),
+        Path::new(CRATE_INPUTS),
+    )
+}
+
+/// The crate's own inputs.
+const CRATE_INPUTS: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/src/inputs");
+
+/// `input_dirs` given the value of `$AOC_INPUT_DIR`, the working directory
+/// and the crate's inputs, which are left out when the working directory's
+/// `src/inputs` is the same place.
+fn search_dirs(env_dir: Option<PathBuf>, cwd: Option<&Path>, crate_dir: &Path) -> Vec<PathBuf> {
+    let mut dirs: Vec<_> = env_dir.into_iter().collect();
+    dirs.push(PathBuf::from("src/inputs"));
+
+    if cwd.is_none_or(|x| x.join("src/inputs") != crate_dir) {
+        dirs.push(crate_dir.to_path_buf());
+    }
+
+    dirs
+}
+
+/// Where downloaded inputs are stored: `$AOC_INPUT_DIR` if it is set,
+/// otherwise the crate's own `src/inputs`.
+pub fn download_dir() -> PathBuf {
+    match env::var_os(INPUT_DIR_VAR) {
+        Some(dir) => PathBuf::from(dir),
+        None => PathBuf::from(CRATE_INPUTS),
+    }
+}
+
+#[derive(Debug)]
+pub enum InputError {
+    NotFound {
+        day: u8,
+        variant: Option<String>,
+        tried: Vec<PathBuf>,
+    },
+    /// Reading failed for a reason other than the file not existing; `path`
+    /// is `None` for stdin.
+    Io {
+        path: Option<PathBuf>,
+        error: io::Error,
+    },
+}
+
+impl Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InputError::NotFound {
+                day,
+                variant,
+                tried,
+            } => {
+                write!(f, "no input found for day {}", day)?;
+                if let Some(variant) = variant {
+                    write!(f, " (variant `{}`)", variant)?;
+                }
+                write!(f, ", tried:")?;
+                for path in tried {
+                    write!(f, "\n  {}", path.display())?;
+                }
+                Ok(())
+            }
+            InputError::Io { path, error } => match path {
+                Some(path) => write!(f, "failed to read {}: {}", path.display(), error),
+                None => write!(f, "failed to read stdin: {}", error),
+            },
+        }
+    }
+}
+
+impl Error for InputError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            InputError::NotFound { .. } => None,
+            InputError::Io { error, .. } => Some(error),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::process;
+
+    use super::*;
+
+    /// A fresh directory with `env`, `cwd/src/inputs` and `crate/src/inputs`
+    /// in it, standing in for the three places inputs are looked up.
+    fn temp_dirs(name: &str) -> (PathBuf, [PathBuf; 3]) {
+        let root = env::temp_dir().join(format!("aoc-input-{}-{}", name, process::id()));
+        let _ = fs::remove_dir_all(&root);
+
+        let dirs = ["env", "cwd/src/inputs", "crate/src/inputs"].map(|x| root.join(x));
+        for dir in &dirs {
+            fs::create_dir_all(dir).unwrap();
+        }
+
+        (root, dirs)
+    }
+
+    fn read_first(dirs: &[PathBuf], day: u8, variant: Option<&str>) -> String {
+        let (_, mut file) = open_first(dirs, day, variant).unwrap();
+        let mut contents = String::new();
+        file.read_to_string(&mut contents).unwrap();
+        contents
+    }
+
+    #[test]
+    fn search_order() {
+        let env_dir = PathBuf::from("/inputs");
+        let crate_dir = Path::new("/aoc/src/inputs");
+
+        assert_eq!(
+            search_dirs(
+                Some(env_dir.clone()),
+                Some(Path::new("/elsewhere")),
+                crate_dir
+            ),
+            [
+                env_dir.clone(),
+                PathBuf::from("src/inputs"),
+                crate_dir.to_path_buf()
+            ]
+        );
+        assert_eq!(
+            search_dirs(None, None, crate_dir),
+            [PathBuf::from("src/inputs"), crate_dir.to_path_buf()]
+        );
+
+        // From the crate root its inputs are only searched once
+        assert_eq!(
+            search_dirs(Some(env_dir.clone()), Some(Path::new("/aoc")), crate_dir),
+            [env_dir, PathBuf::from("src/inputs")]
+        );
+    }
+
+    #[test]
+    fn first_dir_wins() {
+        let (root, dirs) = temp_dirs("order");
+        let [env_dir, cwd_dir, crate_dir] = &dirs;
+
+        for dir in &dirs {
+            fs::write(dir.join("day1.txt"), dir.to_str().unwrap()).unwrap();
+        }
+        assert_eq!(read_first(&dirs, 1, None), env_dir.to_str().unwrap());
+
+        fs::remove_file(env_dir.join("day1.txt")).unwrap();
+        assert_eq!(read_first(&dirs, 1, None), cwd_dir.to_str().unwrap());
+
+        fs::remove_file(cwd_dir.join("day1.txt")).unwrap();
+        let (path, _) = open_first(&dirs, 1, None).unwrap();
+        assert_eq!(path, crate_dir.join("day1.txt"));
+
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn named_variants() {
+        let (root, dirs) = temp_dirs("variants");
+        let [env_dir, _, crate_dir] = &dirs;
+
+        fs::write(env_dir.join("day7.txt"), "input").unwrap();
+        fs::write(crate_dir.join("day7.example.txt"), "example").unwrap();
+        fs::write(env_dir.join("day7.large.txt"), "large").unwrap();
+
+        assert_eq!(read_first(&dirs, 7, None), "input");
+        for variant in ["example", "large"] {
+            assert_eq!(read_first(&dirs, 7, Some(variant)), variant);
+        }
+
+        assert_eq!(file_name(7, None), "day7.txt");
+        assert_eq!(file_name(7, Some("example")), "day7.example.txt");
+
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn not_found_lists_every_path() {
+        let (root, dirs) = temp_dirs("missing");
+
+        let error = open_first(&dirs, 25, Some("missing")).unwrap_err();
+        let InputError::NotFound {
+            day,
+            variant,
+            tried,
+        } = &error
+        else {
+            panic!("expected a missing input, got {:?}", error);
+        };
+        assert_eq!((*day, variant.as_deref()), (25, Some("missing")));
+        assert_eq!(*tried, dirs.map(|x| x.join("day25.missing.txt")));
+
+        let expected = format!(
+            "no input found for day 25 (variant `missing`), tried:\n  {}\n  {}\n  {}",
+            tried[0].display(),
+            tried[1].display(),
+            tried[2].display(),
+        );
+        assert_eq!(error.to_string(), expected);
+
+        let path = root.join("nowhere.txt");
+        let error = Source::Path(path.clone()).read(2).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            format!("no input found for day 2, tried:\n  {}", path.display())
+        );
+
+        fs::remove_dir_all(root).unwrap();
+    }
+}
@@ -0,0 +1,92 @@
+use std::{
+    fmt::{self, Display},
+    path::{Path, PathBuf},
+};
+
+pub mod answers;
+pub mod bench;
+pub mod cancel;
+pub mod client;
+pub mod config;
+pub mod days;
+pub mod differential;
+pub mod generate;
+pub mod geometry;
+pub mod grid;
+pub mod input;
+pub mod parallel;
+pub mod parse;
+pub mod profile;
+pub mod runner;
+pub mod submit;
+pub mod watch;
+
+use generate::Rng;
+pub use parse::ParseError;
+
+/// `name` in the working directory if it exists there, otherwise in the
+/// crate's own directory if it exists there, otherwise in the working
+/// directory, so the runner finds its files from anywhere.
+pub fn project_file(name: &str) -> PathBuf {
+    let local = PathBuf::from(name);
+    if local.exists() {
+        return local;
+    }
+
+    let crate_file = Path::new(env!("CARGO_MANIFEST_DIR")).join(name);
+    if crate_file.exists() {
+        crate_file
+    } else {
+        local
+    }
+}
+
+/// A single day's puzzle, split into parsing and the two parts so that
+/// callers can run and time each phase on its own.
+pub trait Solution {
+    /// Day of the month the puzzle was released on.
+    const DAY: u8;
+
+    /// The parsed representation shared by both parts.
+    type Input;
+    type Part1: Answer;
+    type Part2: Answer;
+
+    fn parse(contents: &str) -> Result<Self::Input, ParseError>;
+
+    fn part1(input: &Self::Input) -> Self::Part1;
+
+    fn part2(input: &Self::Input) -> Self::Part2;
+
+    /// A random input that `parse` accepts and both parts can run on, whose
+    /// size grows with `size`. `None` for days without a generator.
+    fn generate(_rng: &mut Rng, _size: usize) -> Option<String> {
+        None
+    }
+}
+
+/// A value that can be reported as the answer to a part.
+pub trait Answer: Display {
+    fn is_solved(&self) -> bool {
+        true
+    }
+}
+
+impl Answer for u32 {}
+impl Answer for usize {}
+
+/// Answer for a part that has not been solved yet.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Unsolved;
+
+impl Answer for Unsolved {
+    fn is_solved(&self) -> bool {
+        false
+    }
+}
+
+impl Display for Unsolved {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "unsolved")
+    }
+}
@@ -0,0 +1,59 @@
+//! Parallel iteration for the days that use it. With the `sequential`
+//! feature the same calls compile to plain iterators, so answers and timings
+//! can be compared with and without parallelism.
+
+/// `into_par_iter` and `par_iter`, meant to be glob imported.
+pub mod prelude {
+    #[cfg(not(feature = "sequential"))]
+    pub use rayon::iter::{IntoParallelIterator, IntoParallelRefIterator, ParallelIterator};
+
+    #[cfg(feature = "sequential")]
+    pub use super::sequential::{IntoParallelIterator, IntoParallelRefIterator};
+}
+
+/// Sizes the thread pool that parallel iteration runs on. It can only be
+/// set once, before anything has run in parallel.
+pub fn set_threads(threads: usize) -> Result<(), String> {
+    rayon::ThreadPoolBuilder::new()
+        .num_threads(threads)
+        .build_global()
+        .map_err(|x| x.to_string())
+}
+
+/// Number of threads parallel iteration runs on.
+pub fn threads() -> usize {
+    if cfg!(feature = "sequential") {
+        1
+    } else {
+        rayon::current_num_threads()
+    }
+}
+
+/// Stand-ins for rayon's traits that hand back ordinary iterators.
+#[cfg(feature = "sequential")]
+mod sequential {
+    pub trait IntoParallelIterator: IntoIterator + Sized {
+        fn into_par_iter(self) -> Self::IntoIter {
+            self.into_iter()
+        }
+    }
+
+    impl<T: IntoIterator> IntoParallelIterator for T {}
+
+    pub trait IntoParallelRefIterator<'a> {
+        type Iter: Iterator;
+
+        fn par_iter(&'a self) -> Self::Iter;
+    }
+
+    impl<'a, T: 'a + ?Sized> IntoParallelRefIterator<'a> for T
+    where
+        &'a T: IntoIterator,
+    {
+        type Iter = <&'a T as IntoIterator>::IntoIter;
+
+        fn par_iter(&'a self) -> Self::Iter {
+            self.into_iter()
+        }
+    }
+}
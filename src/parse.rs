@@ -0,0 +1,195 @@
+use std::{
+    error::Error,
+    fmt::{self, Display},
+    str::FromStr,
+};
+
+/// Malformed puzzle input, pointing at the offending text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub day: u8,
+    /// 1-based line number.
+    pub line: usize,
+    /// 1-based column, counted in characters.
+    pub column: usize,
+    /// Full text of the offending line, used to render an excerpt. `None`
+    /// when `found` was not part of the input it was reported against.
+    pub source_line: Option<String>,
+    pub found: String,
+    pub expected: String,
+}
+
+impl ParseError {
+    /// Builds an error for `found`, which should be a subslice of `contents`
+    /// (the whole input) so its line and column can be worked out. Anything
+    /// else is reported at line 1, column 1 without an excerpt.
+    pub fn new(day: u8, contents: &str, found: &str, expected: impl Into<String>) -> Self {
+        let start = contents.as_ptr() as usize;
+        let offset = (found.as_ptr() as usize).wrapping_sub(start);
+
+        if offset > contents.len() || found.len() > contents.len() - offset {
+            return Self {
+                day,
+                line: 1,
+                column: 1,
+                source_line: None,
+                found: found.to_string(),
+                expected: expected.into(),
+            };
+        }
+
+        let before = &contents[..offset];
+        let line_start = before.rfind('\n').map_or(0, |x| x + 1);
+        let line_end = contents[offset..]
+            .find('\n')
+            .map_or(contents.len(), |x| offset + x);
+
+        Self {
+            day,
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+            source_line: Some(contents[line_start..line_end].trim_end().to_string()),
+            found: found.to_string(),
+            expected: expected.into(),
+        }
+    }
+
+    /// Renders the offending line with carets under the problem, e.g.
+    ///
+    /// ```text
+    ///   |
+    /// 3 | 7 6 x 2 1
+    ///   |     ^
+    /// ```
+    ///
+    /// `None` when the offending line is not known.
+    pub fn excerpt(&self) -> Option<String> {
+        let source_line = self.source_line.as_ref()?;
+        let number = self.line.to_string();
+        let gutter = " ".repeat(number.len());
+
+        let remaining = source_line.chars().count().saturating_sub(self.column - 1);
+        let width = self
+            .found
+            .lines()
+            .next()
+            .map_or(0, |x| x.chars().count())
+            .min(remaining)
+            .max(1);
+
+        Some(format!(
+            "{} |\n{} | {}\n{} | {}{}",
+            gutter,
+            number,
+            source_line,
+            gutter,
+            " ".repeat(self.column - 1),
+            "^".repeat(width)
+        ))
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "day {}, line {}, column {}: expected {}, found ",
+            self.day, self.line, self.column, self.expected
+        )?;
+
+        if self.found.is_empty() {
+            write!(f, "nothing")
+        } else {
+            write!(f, "`{}`", self.found)
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+/// Parses `token`, a subslice of `contents`, as a number.
+pub fn number<T: FromStr>(
+    day: u8,
+    contents: &str,
+    token: &str,
+    expected: &str,
+) -> Result<T, ParseError> {
+    token
+        .parse()
+        .map_err(|_| ParseError::new(day, contents, token, expected))
+}
+
+/// An empty slice just after the last non-whitespace character of
+/// `contents`, for reporting something that is missing from the input.
+pub fn end_of(contents: &str) -> &str {
+    let end = contents.trim_end().len();
+    &contents[end..end]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn line_and_column() {
+        let contents = "1 2 3\n4 x 6\n";
+        let error = ParseError::new(2, contents, &contents[8..9], "a level");
+
+        assert_eq!((error.line, error.column), (2, 3));
+        assert_eq!(error.source_line.as_deref(), Some("4 x 6"));
+        assert_eq!(
+            error.to_string(),
+            "day 2, line 2, column 3: expected a level, found `x`"
+        );
+    }
+
+    #[test]
+    fn excerpt() {
+        let contents = "7 6 4\n1 2 7\n9 7 6\n7 6 xyz 2 1";
+        let error = number::<u32>(2, contents, &contents[22..25], "a level").unwrap_err();
+
+        assert_eq!(
+            error.excerpt().unwrap(),
+            "  |\n4 | 7 6 xyz 2 1\n  |     ^^^"
+        );
+    }
+
+    #[test]
+    fn multi_byte_characters() {
+        // Columns count characters, not bytes
+        let contents = "é→ 12\nα β γ";
+        let found = &contents[contents.find('γ').unwrap()..];
+        let error = ParseError::new(3, contents, found, "a number");
+
+        assert_eq!((error.line, error.column), (2, 5));
+        assert_eq!(error.excerpt().unwrap(), "  |\n2 | α β γ\n  |     ^");
+    }
+
+    #[test]
+    fn end_of_input() {
+        let contents = "3 4\n5 6\n\n";
+        let error = ParseError::new(1, contents, end_of(contents), "another line");
+
+        assert_eq!((error.line, error.column), (2, 4));
+        assert_eq!(
+            error.to_string(),
+            "day 1, line 2, column 4: expected another line, found nothing"
+        );
+        assert_eq!(error.excerpt().unwrap(), "  |\n2 | 5 6\n  |    ^");
+    }
+
+    #[test]
+    fn slice_of_another_buffer() {
+        let contents = String::from("1 2\n3 4\n");
+        let other = String::from("x");
+
+        let error = ParseError::new(1, &contents, &other, "a location ID");
+        assert_eq!((error.line, error.column), (1, 1));
+        assert_eq!(error.source_line, None);
+        assert_eq!(error.excerpt(), None);
+
+        // Starts inside `contents` but runs past its end
+        let error = ParseError::new(1, &contents[..3], &contents[2..5], "a location ID");
+        assert_eq!(error.source_line, None);
+    }
+}
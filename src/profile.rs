@@ -0,0 +1,287 @@
+//! Wall time of each phase of a day and, when built with the `count-alloc`
+//! feature, what it allocated.
+
+use std::{
+    fmt::{self, Display},
+    time::{Duration, Instant},
+};
+
+use crate::{bench::Phase, runner::Part, Answer, ParseError, Solution};
+
+/// Allocator activity during one phase.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Allocations {
+    /// Allocations made, counting each reallocation as one.
+    pub count: u64,
+    pub bytes: u64,
+    /// Highest number of bytes live at once, over what was live when the
+    /// phase started.
+    pub peak: u64,
+}
+
+#[derive(Debug, Clone)]
+pub struct PhaseProfile {
+    pub day: u8,
+    pub phase: Phase,
+    pub elapsed: Duration,
+    /// `None` without the `count-alloc` feature.
+    pub allocations: Option<Allocations>,
+    /// Whether the phase is parsing or a part with an answer.
+    pub solved: bool,
+}
+
+/// Whether allocations are being counted.
+pub const fn counts_allocations() -> bool {
+    cfg!(feature = "count-alloc")
+}
+
+/// Runs `func`, timing it and counting its allocations if enabled.
+pub fn measure<T, F>(func: F) -> (T, Duration, Option<Allocations>)
+where
+    F: FnOnce() -> T,
+{
+    #[cfg(feature = "count-alloc")]
+    let before = counting::start();
+
+    let start = Instant::now();
+    let value = func();
+    let elapsed = start.elapsed();
+
+    #[cfg(feature = "count-alloc")]
+    let allocations = Some(counting::since(before));
+    #[cfg(not(feature = "count-alloc"))]
+    let allocations = None;
+
+    (value, elapsed, allocations)
+}
+
+pub(crate) fn run<S: Solution>(
+    contents: &str,
+    parts: &[Part],
+) -> Result<Vec<PhaseProfile>, ParseError> {
+    let (input, elapsed, allocations) = measure(|| S::parse(contents));
+    let input = input?;
+
+    let mut results = vec![PhaseProfile {
+        day: S::DAY,
+        phase: Phase::Parse,
+        elapsed,
+        allocations,
+        solved: true,
+    }];
+
+    for &part in parts {
+        let (solved, elapsed, allocations) = match part {
+            Part::One => measure(|| S::part1(&input).is_solved()),
+            Part::Two => measure(|| S::part2(&input).is_solved()),
+        };
+
+        results.push(PhaseProfile {
+            day: S::DAY,
+            phase: Phase::Part(part),
+            elapsed,
+            allocations,
+            solved,
+        });
+    }
+
+    Ok(results)
+}
+
+/// Renders profiles as an aligned table, with a note when allocations were
+/// not counted.
+pub struct Table<'a>(pub &'a [PhaseProfile]);
+
+impl Display for Table<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        const COLUMNS: [&str; 4] = ["Time", "Allocs", "Bytes", "Peak"];
+
+        let rows: Vec<_> = self
+            .0
+            .iter()
+            .map(|x| {
+                let allocations = x.allocations.map_or_else(
+                    || [String::from("-"), String::from("-"), String::from("-")],
+                    |x| [x.count.to_string(), bytes(x.bytes), bytes(x.peak)],
+                );
+                let [count, total, peak] = allocations;
+                [format!("{:.2?}", x.elapsed), count, total, peak]
+            })
+            .collect();
+
+        let widths: Vec<_> = (0..COLUMNS.len())
+            .map(|i| {
+                rows.iter()
+                    .map(|x| x[i].len())
+                    .chain([COLUMNS[i].len()])
+                    .max()
+                    .unwrap_or_default()
+            })
+            .collect();
+
+        write!(f, "Day  Phase ")?;
+        for (column, width) in COLUMNS.iter().zip(&widths) {
+            write!(f, "  {:>width$}", column)?;
+        }
+        writeln!(f)?;
+
+        for (profile, row) in self.0.iter().zip(rows) {
+            write!(f, "{:>3}  {:<6}", profile.day, profile.phase)?;
+            for (value, width) in row.iter().zip(&widths) {
+                write!(f, "  {:>width$}", value)?;
+            }
+            writeln!(f, "{}", if profile.solved { "" } else { "  (unsolved)" })?;
+        }
+
+        if !counts_allocations() {
+            writeln!(
+                f,
+                "\nBuild with `--features count-alloc` to count allocations."
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Formats a byte count with a binary unit.
+fn bytes(count: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+
+    if count < 1024 {
+        return format!("{} B", count);
+    }
+
+    let mut value = count as f64;
+    let mut unit = "B";
+    for x in UNITS {
+        if value < 1024.0 {
+            break;
+        }
+        value /= 1024.0;
+        unit = x;
+    }
+
+    format!("{:.2} {}", value, unit)
+}
+
+/// A global allocator that forwards to the system allocator and counts what
+/// passes through it.
+#[cfg(feature = "count-alloc")]
+mod counting {
+    use std::{
+        alloc::{GlobalAlloc, Layout, System},
+        sync::atomic::{AtomicU64, Ordering},
+    };
+
+    use super::Allocations;
+
+    struct Counting;
+
+    static COUNT: AtomicU64 = AtomicU64::new(0);
+    static BYTES: AtomicU64 = AtomicU64::new(0);
+    static LIVE: AtomicU64 = AtomicU64::new(0);
+    static PEAK: AtomicU64 = AtomicU64::new(0);
+
+    #[global_allocator]
+    static GLOBAL: Counting = Counting;
+
+    fn allocated(size: usize) {
+        COUNT.fetch_add(1, Ordering::Relaxed);
+        BYTES.fetch_add(size as u64, Ordering::Relaxed);
+        let live = LIVE.fetch_add(size as u64, Ordering::Relaxed) + size as u64;
+        PEAK.fetch_max(live, Ordering::Relaxed);
+    }
+
+    fn freed(size: usize) {
+        LIVE.fetch_sub(size as u64, Ordering::Relaxed);
+    }
+
+    unsafe impl GlobalAlloc for Counting {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            let ptr = System.alloc(layout);
+            if !ptr.is_null() {
+                allocated(layout.size());
+            }
+            ptr
+        }
+
+        unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+            let ptr = System.alloc_zeroed(layout);
+            if !ptr.is_null() {
+                allocated(layout.size());
+            }
+            ptr
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            System.dealloc(ptr, layout);
+            freed(layout.size());
+        }
+
+        unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+            let new = System.realloc(ptr, layout, new_size);
+            if !new.is_null() {
+                freed(layout.size());
+                allocated(new_size);
+            }
+            new
+        }
+    }
+
+    /// Counters at the start of a phase.
+    pub struct Start {
+        count: u64,
+        bytes: u64,
+        live: u64,
+    }
+
+    /// Starts a phase, resetting the peak to what is live now.
+    pub fn start() -> Start {
+        let live = LIVE.load(Ordering::Relaxed);
+        PEAK.store(live, Ordering::Relaxed);
+
+        Start {
+            count: COUNT.load(Ordering::Relaxed),
+            bytes: BYTES.load(Ordering::Relaxed),
+            live,
+        }
+    }
+
+    pub fn since(start: Start) -> Allocations {
+        Allocations {
+            count: COUNT.load(Ordering::Relaxed) - start.count,
+            bytes: BYTES.load(Ordering::Relaxed) - start.bytes,
+            peak: PEAK.load(Ordering::Relaxed).saturating_sub(start.live),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn byte_units() {
+        assert_eq!(bytes(0), "0 B");
+        assert_eq!(bytes(1023), "1023 B");
+        assert_eq!(bytes(1536), "1.50 KiB");
+        assert_eq!(bytes(3 * 1024 * 1024), "3.00 MiB");
+    }
+
+    #[cfg(feature = "count-alloc")]
+    #[test]
+    fn count_vec_allocations() {
+        let (_, _, allocations) = measure(|| {
+            let first = vec![0_u8; 1000];
+            let second = vec![0_u8; 500];
+            first.len() + second.len()
+        });
+        let allocations = allocations.unwrap();
+
+        // Other tests allocate concurrently, so only lower bounds hold
+        assert!(allocations.count >= 2);
+        assert!(allocations.bytes >= 1500);
+        assert!(allocations.peak >= 1500);
+    }
+}
@@ -0,0 +1,607 @@
+use std::{
+    fmt::{self, Display},
+    mem,
+    time::{Duration, Instant},
+};
+
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    bench::{self, PhaseStats},
+    cancel::{Deadline, Token},
+    generate::Rng,
+    input,
+    profile::{self, PhaseProfile},
+    Answer, ParseError, Solution,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(try_from = "u8", into = "u8")]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub const ALL: [Part; 2] = [Part::One, Part::Two];
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "1" => Some(Self::One),
+            "2" => Some(Self::Two),
+            _ => None,
+        }
+    }
+}
+
+impl TryFrom<u8> for Part {
+    type Error = String;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            1 => Ok(Self::One),
+            2 => Ok(Self::Two),
+            _ => Err(format!("invalid part {}, expected 1 or 2", value)),
+        }
+    }
+}
+
+impl From<Part> for u8 {
+    fn from(part: Part) -> Self {
+        match part {
+            Part::One => 1,
+            Part::Two => 2,
+        }
+    }
+}
+
+impl Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Part::One => f.pad("1"),
+            Part::Two => f.pad("2"),
+        }
+    }
+}
+
+type RunFn = fn(&str, &[Part], Option<Duration>) -> Result<Vec<PartResult>, ParseError>;
+type BenchFn = fn(&str, &[Part], &bench::Options) -> Result<Vec<PhaseStats>, ParseError>;
+type ProfileFn = fn(&str, &[Part]) -> Result<Vec<PhaseProfile>, ParseError>;
+
+/// Type-erased handle to a day's `Solution`, so days with different input
+/// and answer types can live in the same registry.
+pub struct Day {
+    pub day: u8,
+    run: RunFn,
+    bench: BenchFn,
+    profile: ProfileFn,
+    generate: fn(&mut Rng, usize) -> Option<String>,
+}
+
+impl Day {
+    pub const fn new<S: Solution>() -> Self {
+        Self {
+            day: S::DAY,
+            run: run::<S>,
+            bench: bench::run::<S>,
+            profile: profile::run::<S>,
+            generate: S::generate,
+        }
+    }
+
+    /// Parses `contents` once and runs each of `parts` against it.
+    pub fn run(&self, contents: &str, parts: &[Part]) -> Result<Vec<PartResult>, ParseError> {
+        (self.run)(contents, parts, None)
+    }
+
+    /// Like `run`, but cancels each part that takes longer than `budget` if
+    /// there is one, see `cancel`.
+    pub fn run_with_budget(
+        &self,
+        contents: &str,
+        parts: &[Part],
+        budget: Option<Duration>,
+    ) -> Result<Vec<PartResult>, ParseError> {
+        (self.run)(contents, parts, budget)
+    }
+
+    /// Samples parsing and each of `parts` repeatedly, see `bench::measure`.
+    pub fn bench(
+        &self,
+        contents: &str,
+        parts: &[Part],
+        options: &bench::Options,
+    ) -> Result<Vec<PhaseStats>, ParseError> {
+        (self.bench)(contents, parts, options)
+    }
+
+    /// Runs parsing and each of `parts` once, see `profile::measure`.
+    pub fn profile(&self, contents: &str, parts: &[Part]) -> Result<Vec<PhaseProfile>, ParseError> {
+        (self.profile)(contents, parts)
+    }
+
+    /// A random input from `seed`, see `Solution::generate`.
+    pub fn generate(&self, seed: u64, size: usize) -> Option<String> {
+        (self.generate)(&mut Rng::new(seed), size)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct PartResult {
+    pub day: u8,
+    pub part: Part,
+    /// `None` when the part has not been solved yet or timed out.
+    pub answer: Option<String>,
+    /// Whether the part was cancelled for running past its time budget.
+    pub timed_out: bool,
+    pub elapsed: Duration,
+    /// Time taken to parse the input, which both parts share.
+    pub parse_elapsed: Duration,
+    /// See `input::hash`.
+    pub input_hash: String,
+}
+
+fn run<S: Solution>(
+    contents: &str,
+    parts: &[Part],
+    budget: Option<Duration>,
+) -> Result<Vec<PartResult>, ParseError> {
+    let start = Instant::now();
+    let input = S::parse(contents)?;
+    let parse_elapsed = start.elapsed();
+    let input_hash = input::hash(contents);
+
+    let results = parts
+        .iter()
+        .map(|&part| {
+            let (answer, elapsed, timed_out) = match part {
+                Part::One => timed(budget, || S::part1(&input)),
+                Part::Two => timed(budget, || S::part2(&input)),
+            };
+
+            PartResult {
+                day: S::DAY,
+                part,
+                answer,
+                timed_out,
+                elapsed,
+                parse_elapsed,
+                input_hash: input_hash.clone(),
+            }
+        })
+        .collect();
+
+    Ok(results)
+}
+
+/// Runs `func` with its own cancellation token, cancelled once `budget` has
+/// passed. The answer of a cancelled part is thrown away.
+fn timed<A, F>(budget: Option<Duration>, func: F) -> (Option<String>, Duration, bool)
+where
+    A: Answer,
+    F: FnOnce() -> A,
+{
+    let token = Token::new();
+    let deadline = budget.map(|x| Deadline::start(token.clone(), x));
+
+    let start = Instant::now();
+    let answer = token.scope(func);
+    let elapsed = start.elapsed();
+    drop(deadline);
+
+    if token.is_cancelled() {
+        (None, elapsed, true)
+    } else {
+        (
+            answer.is_solved().then(|| answer.to_string()),
+            elapsed,
+            false,
+        )
+    }
+}
+
+/// How `aoc run` prints its results.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Format {
+    #[default]
+    Table,
+    Json,
+    Csv,
+}
+
+impl Format {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "table" => Some(Self::Table),
+            "json" => Some(Self::Json),
+            "csv" => Some(Self::Csv),
+            _ => None,
+        }
+    }
+
+    pub fn render(&self, results: &[PartResult]) -> String {
+        match self {
+            Format::Table => Table(results).to_string(),
+            Format::Json => Json(results).to_string(),
+            Format::Csv => Csv(results).to_string(),
+        }
+    }
+}
+
+/// Renders results as an aligned day/part/answer/time table.
+pub struct Table<'a>(pub &'a [PartResult]);
+
+impl Display for Table<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let answers: Vec<_> = self
+            .0
+            .iter()
+            .map(|x| x.answer.as_deref().unwrap_or("unsolved"))
+            .collect();
+        let width = answers
+            .iter()
+            .map(|x| x.len())
+            .chain(["Answer".len()])
+            .max()
+            .unwrap_or_default();
+
+        writeln!(f, "Day  Part  {:<width$}  Time", "Answer")?;
+
+        for (result, answer) in self.0.iter().zip(answers) {
+            write!(f, "{:>3}  {:>4}  ", result.day, result.part)?;
+
+            match result.answer {
+                Some(_) => writeln!(f, "{:<width$}  {:?}", answer, result.elapsed)?,
+                None if result.timed_out => writeln!(f, "timed out after {:.2?}", result.elapsed)?,
+                None => writeln!(f, "{}", answer)?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Renders results as a JSON array with one object per part. Answers are
+/// strings, or `null` when unsolved or timed out, and times are in
+/// nanoseconds.
+pub struct Json<'a>(pub &'a [PartResult]);
+
+impl Display for Json<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "[")?;
+
+        for (i, result) in self.0.iter().enumerate() {
+            let answer = match &result.answer {
+                Some(answer) => json_string(answer),
+                None => String::from("null"),
+            };
+
+            write!(
+                f,
+                "  {{\"day\": {}, \"part\": {}, \"answer\": {}, \"timed_out\": {}, \"parse_ns\": {}, \"solve_ns\": {}, \"input_hash\": {}}}",
+                result.day,
+                result.part,
+                answer,
+                result.timed_out,
+                result.parse_elapsed.as_nanos(),
+                result.elapsed.as_nanos(),
+                json_string(&result.input_hash),
+            )?;
+            writeln!(f, "{}", if i + 1 < self.0.len() { "," } else { "" })?;
+        }
+
+        writeln!(f, "]")
+    }
+}
+
+fn json_string(value: &str) -> String {
+    let mut escaped = String::from("\"");
+
+    for c in value.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            c if c.is_control() => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+
+    escaped.push('"');
+    escaped
+}
+
+const CSV_HEADER: &str = "day,part,answer,timed_out,parse_ns,solve_ns,input_hash";
+
+/// Renders results as CSV with a header row. Unsolved and timed out parts
+/// have an empty answer, and times are in nanoseconds.
+pub struct Csv<'a>(pub &'a [PartResult]);
+
+impl Display for Csv<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{}", CSV_HEADER)?;
+
+        for result in self.0 {
+            writeln!(
+                f,
+                "{},{},{},{},{},{},{}",
+                result.day,
+                result.part,
+                csv_field(result.answer.as_deref().unwrap_or_default()),
+                result.timed_out,
+                result.parse_elapsed.as_nanos(),
+                result.elapsed.as_nanos(),
+                result.input_hash,
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Reads back what `Csv` renders.
+pub fn parse_csv(text: &str) -> Result<Vec<PartResult>, String> {
+    let mut records = csv_records(text).into_iter();
+
+    match records.next() {
+        Some(header) if header.join(",") == CSV_HEADER => {}
+        _ => return Err(String::from("missing CSV header")),
+    }
+
+    records
+        .map(|record| {
+            let [day, part, answer, timed_out, parse_ns, solve_ns, input_hash] =
+                <[String; 7]>::try_from(record)
+                    .map_err(|x| format!("expected 7 fields, found {}", x.len()))?;
+            let nanos = |x: &str| {
+                x.parse()
+                    .map(Duration::from_nanos)
+                    .map_err(|_| format!("invalid time `{}`", x))
+            };
+
+            Ok(PartResult {
+                day: day.parse().map_err(|_| format!("invalid day `{}`", day))?,
+                part: Part::parse(&part).ok_or_else(|| format!("invalid part `{}`", part))?,
+                answer: (!answer.is_empty()).then_some(answer),
+                timed_out: timed_out
+                    .parse()
+                    .map_err(|_| format!("invalid timed_out `{}`", timed_out))?,
+                elapsed: nanos(&solve_ns)?,
+                parse_elapsed: nanos(&parse_ns)?,
+                input_hash,
+            })
+        })
+        .collect()
+}
+
+/// Splits CSV into records of unquoted fields.
+fn csv_records(text: &str) -> Vec<Vec<String>> {
+    let mut records = vec![];
+    let mut record = vec![];
+    let mut field = String::new();
+    let mut quoted = false;
+
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        match (quoted, c) {
+            (true, '"') if chars.peek() == Some(&'"') => {
+                chars.next();
+                field.push('"');
+            }
+            (true, '"') => quoted = false,
+            (true, c) => field.push(c),
+            (false, '"') => quoted = true,
+            (false, ',') => record.push(mem::take(&mut field)),
+            (false, '\n') => {
+                record.push(mem::take(&mut field));
+                records.push(mem::take(&mut record));
+            }
+            (false, '\r') => {}
+            (false, c) => field.push(c),
+        }
+    }
+
+    if !field.is_empty() || !record.is_empty() {
+        record.push(field);
+        records.push(record);
+    }
+
+    records
+}
+
+/// A day that could not be run, kept so a run over several days can carry on
+/// past it.
+#[derive(Debug, Clone)]
+pub struct Failure {
+    pub day: u8,
+    pub error: String,
+}
+
+/// Renders the totals of a run over several days: time spent, the slowest
+/// parts and the days that failed.
+pub struct Summary<'a> {
+    pub results: &'a [PartResult],
+    pub failures: &'a [Failure],
+    /// Time taken by the whole run, which is less than the sum of the parts
+    /// when days ran in parallel.
+    pub wall: Duration,
+}
+
+impl Summary<'_> {
+    /// Number of slowest parts listed.
+    pub const SLOWEST: usize = 5;
+}
+
+impl Display for Summary<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let solving: Duration = self.results.iter().map(|x| x.elapsed).sum();
+        // Parts of the same day share one parse
+        let parsing: Duration = self
+            .results
+            .iter()
+            .unique_by(|x| x.day)
+            .map(|x| x.parse_elapsed)
+            .sum();
+        let timed_out = self.results.iter().filter(|x| x.timed_out).count();
+        let unsolved = self
+            .results
+            .iter()
+            .filter(|x| x.answer.is_none() && !x.timed_out)
+            .count();
+
+        writeln!(
+            f,
+            "{} parts, {} unsolved, {} timed out, {} days failed",
+            self.results.len(),
+            unsolved,
+            timed_out,
+            self.failures.len()
+        )?;
+        writeln!(
+            f,
+            "Total {:.2?} solving, {:.2?} parsing, {:.2?} wall time",
+            solving, parsing, self.wall
+        )?;
+
+        let slowest: Vec<_> = self
+            .results
+            .iter()
+            .filter(|x| x.answer.is_some())
+            .sorted_by(|a, b| b.elapsed.cmp(&a.elapsed))
+            .take(Self::SLOWEST)
+            .collect();
+        if !slowest.is_empty() {
+            writeln!(f, "\nSlowest:")?;
+            writeln!(f, "Day  Part  Time")?;
+            for result in slowest {
+                writeln!(
+                    f,
+                    "{:>3}  {:>4}  {:.2?}",
+                    result.day, result.part, result.elapsed
+                )?;
+            }
+        }
+
+        if !self.failures.is_empty() {
+            writeln!(f, "\nFailed:")?;
+            for failure in self.failures.iter().sorted_by_key(|x| x.day) {
+                let mut lines = failure.error.lines();
+                writeln!(
+                    f,
+                    "{:>3}  {}",
+                    failure.day,
+                    lines.next().unwrap_or_default()
+                )?;
+                for line in lines {
+                    writeln!(f, "     {}", line)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn results() -> Vec<PartResult> {
+        vec![
+            PartResult {
+                day: 3,
+                part: Part::One,
+                answer: Some(String::from("a \"b\", c")),
+                timed_out: false,
+                elapsed: Duration::from_nanos(200),
+                parse_elapsed: Duration::from_nanos(100),
+                input_hash: String::from("0123456789abcdef"),
+            },
+            PartResult {
+                day: 3,
+                part: Part::Two,
+                answer: None,
+                timed_out: true,
+                elapsed: Duration::from_nanos(300),
+                parse_elapsed: Duration::from_nanos(100),
+                input_hash: String::from("0123456789abcdef"),
+            },
+        ]
+    }
+
+    #[test]
+    fn json() {
+        assert_eq!(
+            Json(&results()).to_string(),
+            "\
+[
+  {\"day\": 3, \"part\": 1, \"answer\": \"a \\\"b\\\", c\", \"timed_out\": false, \"parse_ns\": 100, \"solve_ns\": 200, \"input_hash\": \"0123456789abcdef\"},
+  {\"day\": 3, \"part\": 2, \"answer\": null, \"timed_out\": true, \"parse_ns\": 100, \"solve_ns\": 300, \"input_hash\": \"0123456789abcdef\"}
+]
+"
+        );
+    }
+
+    #[test]
+    fn csv() {
+        assert_eq!(
+            Csv(&results()).to_string(),
+            "\
+day,part,answer,timed_out,parse_ns,solve_ns,input_hash
+3,1,\"a \"\"b\"\", c\",false,100,200,0123456789abcdef
+3,2,,true,100,300,0123456789abcdef
+"
+        );
+    }
+
+    #[test]
+    fn csv_round_trip() {
+        let parsed = parse_csv(&Csv(&results()).to_string()).unwrap();
+
+        assert_eq!(format!("{:?}", parsed), format!("{:?}", results()),);
+        assert!(parse_csv("1,2,3\n").is_err());
+    }
+
+    #[test]
+    fn summary() {
+        let mut results = results();
+        results[0].elapsed = Duration::from_millis(3);
+        results[1].answer = Some(String::from("12"));
+        results[1].timed_out = false;
+        let failures = [Failure {
+            day: 5,
+            error: String::from("expected a number\n1 | x"),
+        }];
+
+        assert_eq!(
+            Summary {
+                results: &results,
+                failures: &failures,
+                wall: Duration::from_millis(4),
+            }
+            .to_string(),
+            "\
+2 parts, 0 unsolved, 0 timed out, 1 days failed
+Total 3.00ms solving, 100.00ns parsing, 4.00ms wall time
+
+Slowest:
+Day  Part  Time
+  3     1  3.00ms
+  3     2  300.00ns
+
+Failed:
+  5  expected a number
+     1 | x
+"
+        );
+    }
+}
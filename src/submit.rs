@@ -0,0 +1,478 @@
+//! Submitting answers, and remembering enough about earlier submissions to
+//! refuse ones that are certain to be wrong.
+
+use std::{
+    error::Error,
+    fmt::{self, Display},
+    fs, io,
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    answers::{Answers, Entry},
+    client::{Client, ClientError},
+    config::Config,
+    runner::Part,
+};
+
+/// Local record of rejected answers and cooldowns, kept out of git.
+pub const HISTORY_FILE: &str = "submissions.toml";
+
+/// What the site said about a submitted answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    Correct,
+    TooHigh,
+    TooLow,
+    /// Wrong, without saying in which direction.
+    Wrong,
+    /// Answered too recently, try again after this long.
+    Wait(Duration),
+    /// The part has already been solved, or is not unlocked yet.
+    AlreadySolved,
+    /// A page that could not be understood, as plain text.
+    Unknown(String),
+}
+
+impl Outcome {
+    /// Reads the outcome from the page returned for a submission.
+    pub fn parse(page: &str) -> Self {
+        let text = article_text(page);
+
+        if text.contains("That's the right answer") {
+            Self::Correct
+        } else if text.contains("You gave an answer too recently") {
+            Self::Wait(parse_wait(&text).unwrap_or(Duration::from_secs(60)))
+        } else if text.contains("That's not the right answer") {
+            if text.contains("your answer is too high") {
+                Self::TooHigh
+            } else if text.contains("your answer is too low") {
+                Self::TooLow
+            } else {
+                Self::Wrong
+            }
+        } else if text.contains("You don't seem to be solving the right level") {
+            Self::AlreadySolved
+        } else {
+            Self::Unknown(text)
+        }
+    }
+}
+
+impl Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Outcome::Correct => write!(f, "correct"),
+            Outcome::TooHigh => write!(f, "wrong, too high"),
+            Outcome::TooLow => write!(f, "wrong, too low"),
+            Outcome::Wrong => write!(f, "wrong"),
+            Outcome::Wait(duration) => write!(f, "answered too recently, wait {:?}", duration),
+            Outcome::AlreadySolved => write!(f, "already solved, or not unlocked yet"),
+            Outcome::Unknown(text) => write!(f, "unrecognised response: {}", text),
+        }
+    }
+}
+
+/// The text of the page's `<article>`, which holds the verdict, with tags
+/// removed and whitespace collapsed.
+fn article_text(page: &str) -> String {
+    let article = page
+        .split_once("<article")
+        .and_then(|(_, x)| x.split_once('>'))
+        .map_or(page, |(_, x)| x.split("</article>").next().unwrap_or(x));
+
+    let mut text = String::new();
+    let mut in_tag = false;
+    for c in article.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            _ if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Reads the cooldown from `You have 1m 23s left to wait`.
+fn parse_wait(text: &str) -> Option<Duration> {
+    let (_, rest) = text.split_once("You have ")?;
+    let (amount, _) = rest.split_once(" left to wait")?;
+
+    let mut seconds = 0;
+    for x in amount.split_whitespace() {
+        let (value, scale) = match x.strip_suffix('m') {
+            Some(value) => (value, 60),
+            None => (x.strip_suffix('s')?, 1),
+        };
+        seconds += value.parse::<u64>().ok()? * scale;
+    }
+
+    Some(Duration::from_secs(seconds))
+}
+
+/// A direction the site gave for a rejected answer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Hint {
+    TooHigh,
+    TooLow,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Rejected {
+    pub day: u8,
+    pub part: Part,
+    pub answer: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hint: Option<Hint>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct History {
+    /// Seconds since the epoch before which the site will not take answers.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    cooldown_until: Option<u64>,
+    #[serde(default)]
+    rejected: Vec<Rejected>,
+}
+
+impl History {
+    pub fn load(path: &Path) -> Result<Self, SubmitError> {
+        let contents = match fs::read_to_string(path) {
+            Ok(contents) => contents,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(Self::default()),
+            Err(error) => {
+                return Err(SubmitError::Io {
+                    path: path.to_path_buf(),
+                    error,
+                })
+            }
+        };
+
+        toml::from_str(&contents).map_err(|error| SubmitError::Parse {
+            path: path.to_path_buf(),
+            error,
+        })
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), SubmitError> {
+        let contents = toml::to_string(self).expect("history is always valid TOML");
+
+        fs::write(path, contents).map_err(|error| SubmitError::Io {
+            path: path.to_path_buf(),
+            error,
+        })
+    }
+
+    /// Why `answer` should not be submitted, if it is certain to be
+    /// rejected or the site is still cooling down.
+    pub fn check(&self, day: u8, part: Part, answer: &str, now: SystemTime) -> Option<Refusal> {
+        let now = seconds(now);
+        if let Some(until) = self.cooldown_until.filter(|&x| x > now) {
+            return Some(Refusal::Cooldown(Duration::from_secs(until - now)));
+        }
+
+        let rejected = self
+            .rejected
+            .iter()
+            .filter(|x| x.day == day && x.part == part);
+        let value = answer.parse::<i128>().ok();
+
+        for x in rejected {
+            if x.answer == answer {
+                return Some(Refusal::Rejected(x.clone()));
+            }
+
+            let bound = match x.answer.parse::<i128>() {
+                Ok(bound) => bound,
+                Err(_) => continue,
+            };
+            let outside = match (x.hint, value) {
+                (Some(Hint::TooHigh), Some(value)) => value > bound,
+                (Some(Hint::TooLow), Some(value)) => value < bound,
+                _ => false,
+            };
+            if outside {
+                return Some(Refusal::Rejected(x.clone()));
+            }
+        }
+
+        None
+    }
+
+    /// Remembers what the site said about `answer`.
+    pub fn record(
+        &mut self,
+        day: u8,
+        part: Part,
+        answer: &str,
+        outcome: &Outcome,
+        now: SystemTime,
+    ) {
+        if let Outcome::Wait(duration) = outcome {
+            self.cooldown_until = Some(seconds(now) + duration.as_secs());
+        }
+
+        let hint = match outcome {
+            Outcome::TooHigh => Some(Hint::TooHigh),
+            Outcome::TooLow => Some(Hint::TooLow),
+            Outcome::Wrong => None,
+            _ => return,
+        };
+
+        self.rejected.push(Rejected {
+            day,
+            part,
+            answer: answer.to_string(),
+            hint,
+        });
+    }
+}
+
+fn seconds(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+pub fn default_history_path() -> PathBuf {
+    crate::project_file(HISTORY_FILE)
+}
+
+/// Why a submission was not sent.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Refusal {
+    /// Already rejected, or on the wrong side of a rejected bound.
+    Rejected(Rejected),
+    Cooldown(Duration),
+    /// The part already has an accepted answer.
+    Accepted(String),
+}
+
+impl Display for Refusal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Refusal::Rejected(Rejected {
+                answer, hint: None, ..
+            }) => write!(f, "{} was already rejected", answer),
+            Refusal::Rejected(Rejected {
+                answer,
+                hint: Some(Hint::TooHigh),
+                ..
+            }) => write!(f, "{} was already rejected as too high", answer),
+            Refusal::Rejected(Rejected {
+                answer,
+                hint: Some(Hint::TooLow),
+                ..
+            }) => write!(f, "{} was already rejected as too low", answer),
+            Refusal::Cooldown(duration) => {
+                write!(f, "answered too recently, wait another {:?}", duration)
+            }
+            Refusal::Accepted(answer) => write!(f, "{} was already accepted", answer),
+        }
+    }
+}
+
+/// Submits `answer` unless the history or the registry rule it out,
+/// recording the outcome in both. The caller saves them afterwards.
+pub fn submit(
+    config: &Config,
+    answers: &mut Answers,
+    history: &mut History,
+    entry: Entry,
+) -> Result<Outcome, SubmitError> {
+    if let Some(accepted) = answers.get(entry.day, entry.part) {
+        return Err(SubmitError::Refused(Refusal::Accepted(
+            accepted.answer.clone(),
+        )));
+    }
+
+    let now = SystemTime::now();
+    if let Some(refusal) = history.check(entry.day, entry.part, &entry.answer, now) {
+        return Err(SubmitError::Refused(refusal));
+    }
+
+    let page = Client::new(config)?.submit(entry.day, entry.part, &entry.answer)?;
+    let outcome = Outcome::parse(&page);
+
+    history.record(entry.day, entry.part, &entry.answer, &outcome, now);
+    if outcome == Outcome::Correct {
+        answers.record(entry);
+    }
+
+    Ok(outcome)
+}
+
+#[derive(Debug)]
+pub enum SubmitError {
+    Refused(Refusal),
+    Client(ClientError),
+    Io {
+        path: PathBuf,
+        error: io::Error,
+    },
+    Parse {
+        path: PathBuf,
+        error: toml::de::Error,
+    },
+}
+
+impl From<ClientError> for SubmitError {
+    fn from(error: ClientError) -> Self {
+        Self::Client(error)
+    }
+}
+
+impl Display for SubmitError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SubmitError::Refused(refusal) => write!(f, "not submitting, {}", refusal),
+            SubmitError::Client(error) => write!(f, "{}", error),
+            SubmitError::Io { path, error } => {
+                write!(f, "failed to access {}: {}", path.display(), error)
+            }
+            SubmitError::Parse { path, error } => {
+                write!(
+                    f,
+                    "invalid submission history {}: {}",
+                    path.display(),
+                    error
+                )
+            }
+        }
+    }
+}
+
+impl Error for SubmitError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            SubmitError::Refused(_) => None,
+            SubmitError::Client(error) => Some(error),
+            SubmitError::Io { error, .. } => Some(error),
+            SubmitError::Parse { error, .. } => Some(error),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::tests::serve_once;
+
+    fn page(article: &str) -> String {
+        format!(
+            "<html><body><main>\n<article><p>{}</p></article>\n</main></body></html>",
+            article
+        )
+    }
+
+    #[test]
+    fn outcomes() {
+        let cases = [
+            (
+                "That's the right answer!  You are <span class=\"day-success\">one gold star</span> closer.",
+                Outcome::Correct,
+            ),
+            (
+                "That's not the right answer; your answer is too high.  Please wait one minute before trying again.",
+                Outcome::TooHigh,
+            ),
+            (
+                "That's not the right answer; your answer is too low.",
+                Outcome::TooLow,
+            ),
+            (
+                "That's not the right answer.  If you're stuck, make sure you're using the full input data.",
+                Outcome::Wrong,
+            ),
+            (
+                "You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 1m 23s left to wait.",
+                Outcome::Wait(Duration::from_secs(83)),
+            ),
+            (
+                "You gave an answer too recently.  You have 45s left to wait.",
+                Outcome::Wait(Duration::from_secs(45)),
+            ),
+            (
+                "You don't seem to be solving the right level.  Did you already complete it?",
+                Outcome::AlreadySolved,
+            ),
+        ];
+
+        for (article, outcome) in cases {
+            assert_eq!(Outcome::parse(&page(article)), outcome, "{}", article);
+        }
+    }
+
+    #[test]
+    fn refuse_known_wrong_answers() {
+        let now = UNIX_EPOCH + Duration::from_secs(1_000_000);
+        let mut history = History::default();
+
+        history.record(1, Part::One, "100", &Outcome::TooHigh, now);
+        history.record(1, Part::One, "50", &Outcome::TooLow, now);
+        history.record(1, Part::One, "75", &Outcome::Wrong, now);
+
+        let check = |answer| history.check(1, Part::One, answer, now);
+        assert!(check("100").is_some());
+        assert!(check("150").is_some());
+        assert!(check("20").is_some());
+        assert!(check("75").is_some());
+        assert_eq!(check("76"), None);
+        assert_eq!(history.check(1, Part::Two, "150", now), None);
+
+        history.record(
+            1,
+            Part::One,
+            "76",
+            &Outcome::Wait(Duration::from_secs(30)),
+            now,
+        );
+        assert_eq!(
+            history.check(1, Part::One, "77", now + Duration::from_secs(10)),
+            Some(Refusal::Cooldown(Duration::from_secs(20)))
+        );
+        assert_eq!(
+            history.check(1, Part::One, "77", now + Duration::from_secs(30)),
+            None
+        );
+    }
+
+    #[test]
+    fn submit_to_local_server() {
+        let (url, server) = serve_once(
+            "200 OK",
+            "<article><p>That's the right answer!</p></article>",
+        );
+        let config = Config {
+            session: Some(String::from("abc123")),
+            base_url: Some(url),
+            contact: None,
+        };
+        let mut answers = Answers::default();
+        let mut history = History::default();
+        let entry = Entry {
+            day: 3,
+            part: Part::Two,
+            answer: String::from("48"),
+            input_hash: None,
+        };
+
+        let outcome = submit(&config, &mut answers, &mut history, entry.clone()).unwrap();
+        let request = server.join().unwrap();
+
+        assert_eq!(outcome, Outcome::Correct);
+        assert!(request.starts_with("POST /2024/day/3/answer "));
+        assert!(request.ends_with("level=2&answer=48"));
+        assert_eq!(answers.get(3, Part::Two), Some(&entry));
+
+        // Nothing is listening any more, so the registry has to refuse this
+        let error = submit(&config, &mut answers, &mut history, entry).unwrap_err();
+        assert!(matches!(error, SubmitError::Refused(Refusal::Accepted(_))));
+    }
+}
@@ -0,0 +1,226 @@
+//! Polling files for changes and comparing answers between runs, for
+//! `aoc watch`.
+
+use std::{
+    fmt::{self, Display},
+    fs,
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+
+use crate::runner::PartResult;
+
+/// Notices files being modified, created or removed by comparing their
+/// modification times between polls, which works the same everywhere. A
+/// directory counts as changed when anything inside it does.
+pub struct Watcher {
+    files: Vec<(PathBuf, Option<SystemTime>)>,
+}
+
+impl Watcher {
+    pub fn new(paths: impl IntoIterator<Item = PathBuf>) -> Self {
+        Self {
+            files: paths
+                .into_iter()
+                .map(|path| {
+                    let modified = modified(&path);
+                    (path, modified)
+                })
+                .collect(),
+        }
+    }
+
+    /// Returns the files that changed since the last poll.
+    pub fn poll(&mut self) -> Vec<&Path> {
+        let mut changed = vec![];
+
+        for (path, last) in &mut self.files {
+            let modified = modified(path);
+            if modified != *last {
+                *last = modified;
+                changed.push(path.as_path());
+            }
+        }
+
+        changed
+    }
+}
+
+/// The modification time of a file, or the latest one of a directory and
+/// everything in it. Adding or removing an entry updates the time of the
+/// directory it was in.
+fn modified(path: &Path) -> Option<SystemTime> {
+    let metadata = fs::metadata(path).ok()?;
+    let modified = metadata.modified().ok();
+    if !metadata.is_dir() {
+        return modified;
+    }
+
+    let entries = fs::read_dir(path).into_iter().flatten().flatten();
+    entries
+        .filter_map(|x| self::modified(&x.path()))
+        .chain(modified)
+        .max()
+}
+
+/// Renders the results of a run next to how each answer compares with the
+/// run before it.
+pub struct Diff<'a> {
+    pub previous: Option<&'a [PartResult]>,
+    pub current: &'a [PartResult],
+}
+
+impl Display for Diff<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let answer = |x: &PartResult| x.answer.clone().unwrap_or_else(|| String::from("unsolved"));
+
+        let rows: Vec<_> = self
+            .current
+            .iter()
+            .map(|x| {
+                let change = self.previous.map(|previous| {
+                    match previous.iter().find(|y| y.day == x.day && y.part == x.part) {
+                        Some(y) if y.answer == x.answer => String::from("unchanged"),
+                        Some(y) => format!("was {}", answer(y)),
+                        None => String::from("new"),
+                    }
+                });
+
+                (answer(x), format!("{:.2?}", x.elapsed), change)
+            })
+            .collect();
+
+        let width = |column: fn(&(String, String, Option<String>)) -> usize, title: &str| {
+            rows.iter()
+                .map(column)
+                .chain([title.len()])
+                .max()
+                .unwrap_or_default()
+        };
+        let answer_width = width(|x| x.0.chars().count(), "Answer");
+        let time_width = width(|x| x.1.chars().count(), "Time");
+
+        write!(
+            f,
+            "Day  Part  {:<answer_width$}  {:>time_width$}",
+            "Answer", "Time"
+        )?;
+        writeln!(
+            f,
+            "{}",
+            if self.previous.is_some() {
+                "  Change"
+            } else {
+                ""
+            }
+        )?;
+
+        for (result, (answer, time, change)) in self.current.iter().zip(rows) {
+            write!(
+                f,
+                "{:>3}  {:>4}  {:<answer_width$}  {:>time_width$}",
+                result.day, result.part, answer, time
+            )?;
+            match change {
+                Some(change) => writeln!(f, "  {}", change)?,
+                None => writeln!(f)?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{process, time::Duration};
+
+    use super::*;
+    use crate::runner::Part;
+
+    fn result(part: Part, answer: Option<&str>) -> PartResult {
+        PartResult {
+            day: 2,
+            part,
+            answer: answer.map(String::from),
+            timed_out: false,
+            elapsed: Duration::from_micros(15),
+            parse_elapsed: Duration::ZERO,
+            input_hash: String::new(),
+        }
+    }
+
+    #[test]
+    fn diff() {
+        let previous = [result(Part::One, Some("486")), result(Part::Two, None)];
+        let current = [
+            result(Part::One, Some("486")),
+            result(Part::Two, Some("540")),
+        ];
+
+        assert_eq!(
+            Diff {
+                previous: Some(&previous),
+                current: &current,
+            }
+            .to_string(),
+            "\
+Day  Part  Answer     Time  Change
+  2     1  486     15.00µs  unchanged
+  2     2  540     15.00µs  was unsolved
+"
+        );
+    }
+
+    #[test]
+    fn poll() {
+        let path = std::env::temp_dir().join(format!("aoc-watch-{}.txt", process::id()));
+        let _ = fs::remove_file(&path);
+
+        let mut watcher = Watcher::new([path.clone()]);
+        assert!(watcher.poll().is_empty());
+
+        fs::write(&path, "1").unwrap();
+        assert_eq!(watcher.poll(), [path.as_path()]);
+        assert!(watcher.poll().is_empty());
+
+        fs::remove_file(&path).unwrap();
+        assert_eq!(watcher.poll(), [path.as_path()]);
+    }
+
+    #[test]
+    fn poll_directory() {
+        let dir = std::env::temp_dir().join(format!("aoc-watch-{}", process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join("nested")).unwrap();
+        let file = dir.join("nested/part1.rs");
+        fs::write(&file, "1").unwrap();
+
+        // Modification times are set by hand as they can be too coarse to
+        // tell quick writes apart, and ahead of the directories' own
+        let time = SystemTime::now() + Duration::from_secs(3600);
+        let touch = |path: &Path, secs| {
+            fs::File::options()
+                .write(true)
+                .open(path)
+                .unwrap()
+                .set_modified(time + Duration::from_secs(secs))
+                .unwrap();
+        };
+        touch(&file, 0);
+
+        let mut watcher = Watcher::new([dir.clone()]);
+        assert!(watcher.poll().is_empty());
+
+        fs::write(&file, "2").unwrap();
+        touch(&file, 1);
+        assert_eq!(watcher.poll(), [dir.as_path()]);
+        assert!(watcher.poll().is_empty());
+
+        fs::remove_file(&file).unwrap();
+        assert_eq!(watcher.poll(), [dir.as_path()]);
+
+        fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(watcher.poll(), [dir.as_path()]);
+    }
+}